@@ -0,0 +1,311 @@
+/*
+ * FINAL FIOLET ENGINE — C ABI CONFORMANCE TEST
+ *
+ * Asserts the contract in docs/ABI_CONTRACT.md from the C side,
+ * linked against the no_std staticlib (libfiolet_core.a).
+ *
+ * Build and run: scripts/abi_conformance.sh
+ *
+ * Exit status 0 iff every check passed. The build provenance
+ * record is printed so a run can be tied to the kernel it tested.
+ */
+
+#include "fiolet_core.h"
+
+#include <inttypes.h>
+#include <math.h>
+#include <stdio.h>
+#include <string.h>
+
+/*
+ * The no_std kernel is built with panic = abort and never unwinds,
+ * but the precompiled libcore still references this symbol.
+ * It is never called.
+ */
+void rust_eh_personality(void) {}
+
+static int failures = 0;
+
+#define CHECK(cond)                                                   \
+    do {                                                              \
+        if (!(cond)) {                                                \
+            fprintf(stderr, "FAIL %s:%d: %s\n", __FILE__, __LINE__, #cond); \
+            failures++;                                               \
+        }                                                             \
+    } while (0)
+
+static void check_version_and_provenance(void) {
+    uint32_t version = fiolet_abi_version();
+    CHECK((version >> 16) == FIOLET_ABI_VERSION_MAJOR);
+    CHECK(version >= FIOLET_ABI_VERSION);
+
+    uint64_t required = FIOLET_CAP_HANDLES | FIOLET_CAP_CONFIG | FIOLET_CAP_BATCH
+                      | FIOLET_CAP_HALT_CAUSE | FIOLET_CAP_MULTI_CHANNEL
+                      | FIOLET_CAP_FIXED_POINT | FIOLET_CAP_SELF_CHECK
+                      | FIOLET_CAP_TMR | FIOLET_CAP_HALT_CALLBACK
+                      | FIOLET_CAP_BASELINE_BAND;
+    CHECK((fiolet_capabilities() & required) == required);
+
+    FioletBuildInfo info;
+    CHECK(!fiolet_build_info(NULL));
+    CHECK(fiolet_build_info(&info));
+    CHECK(info.abi_version == version);
+    CHECK(info.capabilities == fiolet_capabilities());
+    CHECK(info.fingerprint == fiolet_build_fingerprint());
+    CHECK(info.features & FIOLET_FEATURE_KERNEL);
+    CHECK(!(info.features & FIOLET_FEATURE_STD));
+
+    const char* crate_version = fiolet_crate_version();
+    CHECK(crate_version != NULL && strlen(crate_version) > 0);
+
+    printf("abi_version  %" PRIu32 ".%" PRIu32 "\n", version >> 16, version & 0xFFFF);
+    printf("crate        %s\n", crate_version);
+    printf("features     0x%08" PRIx32 "\n", info.features);
+    printf("capabilities 0x%016" PRIx64 "\n", info.capabilities);
+    printf("config_hash  0x%016" PRIx64 "\n", info.config_hash);
+    printf("fingerprint  0x%016" PRIx64 "\n", info.fingerprint);
+}
+
+static void check_monotonic_halt(void) {
+    FioletKernel* k = fiolet_kernel_new(1.0f);
+    CHECK(k != NULL);
+    CHECK(!fiolet_kernel_is_halted(k));
+
+    CHECK(fiolet_kernel_evaluate(k, 0.5f) == FIOLET_CONTINUE);
+    CHECK(fiolet_kernel_evaluate(k, 1.0f) == FIOLET_CONTINUE);
+    CHECK(fiolet_kernel_evaluate(k, 2.0f) == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_kernel_evaluate(k, 0.0f) == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_kernel_is_halted(k));
+    CHECK(fiolet_kernel_steps(k) == 3);
+
+    FioletHaltCause cause;
+    CHECK(fiolet_kernel_halt_cause(k, &cause));
+    CHECK(cause.reason == FIOLET_HALT_THRESHOLD_EXCEEDED);
+    CHECK(cause.value == 2.0f);
+    CHECK(cause.step == 2);
+    CHECK(cause.channel == FIOLET_NO_CHANNEL);
+
+    fiolet_kernel_free(k);
+}
+
+static void check_fail_closed(void) {
+    FioletKernel* k = fiolet_kernel_new(1.0f);
+    CHECK(fiolet_kernel_evaluate(k, NAN) == FIOLET_ATOMIC_HALT);
+    fiolet_kernel_free(k);
+
+    k = fiolet_kernel_new(-1.0f);
+    CHECK(fiolet_kernel_is_halted(k));
+    fiolet_kernel_free(k);
+
+    FioletSafetyConfig config = { 1.0f, FIOLET_NO_STEP_BUDGET, { 0.5f, 4, 3 }, FIOLET_NO_SLOPE_LIMIT };
+    CHECK(fiolet_config_validate(config) == FIOLET_CONFIG_INVALID_WINDOW);
+    k = fiolet_kernel_new_with_config(config);
+    CHECK(fiolet_kernel_evaluate(k, 0.0f) == FIOLET_ATOMIC_HALT);
+    fiolet_kernel_free(k);
+}
+
+static void check_invalid_handles(void) {
+    CHECK(fiolet_kernel_evaluate(NULL, 0.0f) == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_kernel_is_halted(NULL));
+    fiolet_kernel_free(NULL);
+
+    FioletKernel* forged = (FioletKernel*)(uintptr_t)0xF1000105u;
+    CHECK(fiolet_kernel_evaluate(forged, 0.0f) == FIOLET_ATOMIC_HALT);
+
+    FioletKernel* k = fiolet_kernel_new(1.0f);
+    fiolet_kernel_free(k);
+    CHECK(fiolet_kernel_evaluate(k, 0.0f) == FIOLET_ATOMIC_HALT);
+
+    FioletHaltCause cause;
+    CHECK(fiolet_kernel_halt_cause(k, &cause));
+    CHECK(cause.reason == FIOLET_HALT_INVALID_INPUT);
+
+    /* A reused slot must not be reachable through the stale handle. */
+    FioletKernel* fresh = fiolet_kernel_new(1.0f);
+    CHECK(fiolet_kernel_evaluate(k, 0.0f) == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_kernel_evaluate(fresh, 0.0f) == FIOLET_CONTINUE);
+    fiolet_kernel_free(fresh);
+}
+
+static void check_pool_exhaustion(void) {
+    FioletKernel* kernels[FIOLET_MAX_KERNELS];
+    for (size_t i = 0; i < FIOLET_MAX_KERNELS; i++) {
+        kernels[i] = fiolet_kernel_new(1.0f);
+        CHECK(kernels[i] != NULL);
+    }
+    CHECK(fiolet_kernel_new(1.0f) == NULL);
+    for (size_t i = 0; i < FIOLET_MAX_KERNELS; i++) {
+        fiolet_kernel_free(kernels[i]);
+    }
+}
+
+static void check_batch(void) {
+    FioletKernel* k = fiolet_kernel_new(1.0f);
+    const float inputs[] = { 0.1f, 0.2f, 3.0f, 0.0f };
+    size_t first_halt = 0;
+
+    CHECK(fiolet_kernel_evaluate_slice(k, inputs, 2, &first_halt) == FIOLET_CONTINUE);
+    CHECK(first_halt == FIOLET_NO_HALT_INDEX);
+    CHECK(fiolet_kernel_evaluate_slice(k, inputs, 4, &first_halt) == FIOLET_ATOMIC_HALT);
+    CHECK(first_halt == 2);
+    fiolet_kernel_free(k);
+
+    k = fiolet_kernel_new(1.0f);
+    CHECK(fiolet_kernel_evaluate_slice(k, NULL, 4, &first_halt) == FIOLET_ATOMIC_HALT);
+    CHECK(first_halt == 0);
+    fiolet_kernel_free(k);
+}
+
+static void check_multi_and_fixed(void) {
+    const float limits[] = { 1.0f, 0.5f };
+    FioletMultiKernel* m = fiolet_multi_kernel_new(limits, 2);
+    const float ok[] = { 0.9f, 0.4f };
+    const float bad[] = { 0.0f, 0.6f };
+    CHECK(fiolet_multi_kernel_evaluate(m, ok, 2) == FIOLET_CONTINUE);
+    CHECK(fiolet_multi_kernel_evaluate(m, bad, 2) == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_multi_kernel_tripped_channel(m) == 1);
+    fiolet_multi_kernel_free(m);
+    CHECK(fiolet_multi_kernel_is_halted(m));
+
+    m = fiolet_multi_kernel_new(limits, 0);
+    CHECK(fiolet_multi_kernel_is_halted(m));
+    FioletHaltCause zero;
+    CHECK(fiolet_multi_kernel_halt_cause(m, &zero));
+    CHECK(zero.reason == FIOLET_HALT_INVALID_CONFIG);
+    CHECK(fiolet_multi_kernel_evaluate(m, ok, 0) == FIOLET_ATOMIC_HALT);
+    fiolet_multi_kernel_free(m);
+
+    FioletFixedKernel* f = fiolet_fixed_kernel_new(FIOLET_Q16_ONE);
+    CHECK(fiolet_fixed_kernel_evaluate(f, FIOLET_Q16_ONE) == FIOLET_CONTINUE);
+    CHECK(fiolet_fixed_kernel_evaluate(f, FIOLET_Q16_ONE + 1) == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_fixed_kernel_evaluate(f, 0) == FIOLET_ATOMIC_HALT);
+    fiolet_fixed_kernel_free(f);
+}
+
+static void check_tmr(void) {
+    FioletSafetyConfig config = { 1.0f, FIOLET_NO_STEP_BUDGET, { 0.0f, 0, 0 }, FIOLET_NO_SLOPE_LIMIT };
+    FioletTmrKernel* t = fiolet_tmr_kernel_new(config);
+    CHECK(t != NULL);
+    CHECK(fiolet_tmr_kernel_evaluate(t, 0.5f) == FIOLET_CONTINUE);
+    CHECK(fiolet_tmr_kernel_evaluate(t, 1.5f) == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_tmr_kernel_evaluate(t, 0.0f) == FIOLET_ATOMIC_HALT);
+
+    FioletHaltCause cause;
+    CHECK(fiolet_tmr_kernel_halt_cause(t, &cause));
+    CHECK(cause.reason == FIOLET_HALT_THRESHOLD_EXCEEDED);
+    fiolet_tmr_kernel_free(t);
+    CHECK(fiolet_tmr_kernel_evaluate(t, 0.0f) == FIOLET_ATOMIC_HALT);
+}
+
+static void check_baseline_band(void) {
+    /* Zero band fields: same meaning as FioletSafetyConfig. */
+    FioletSafetyConfigV2 config = { { 2.0f, FIOLET_NO_STEP_BUDGET, { 0.0f, 0, 0 }, FIOLET_NO_SLOPE_LIMIT },
+                                    0.0f, 0.0f, 0 };
+    CHECK(fiolet_config_validate_v2(config) == FIOLET_CONFIG_OK);
+    FioletKernel* k = fiolet_kernel_new_with_config_v2(config);
+    CHECK(fiolet_kernel_evaluate(k, -5.0f) == FIOLET_CONTINUE);
+    CHECK(fiolet_kernel_evaluate(k, 2.5f) == FIOLET_ATOMIC_HALT);
+    fiolet_kernel_free(k);
+
+    config.baseline = 10.0f;
+    config.lower_limit = 0.5f;
+    config.lower_bounded = 1;
+    CHECK(fiolet_config_validate_v2(config) == FIOLET_CONFIG_OK);
+
+    k = fiolet_kernel_new_with_config_v2(config);
+    CHECK(fiolet_kernel_evaluate(k, 12.0f) == FIOLET_CONTINUE);
+    CHECK(fiolet_kernel_evaluate(k, 9.5f) == FIOLET_CONTINUE);
+    CHECK(fiolet_kernel_evaluate(k, 9.25f) == FIOLET_ATOMIC_HALT);
+    fiolet_kernel_free(k);
+
+    k = fiolet_kernel_new_with_config_v2(config);
+    CHECK(fiolet_kernel_evaluate(k, NAN) == FIOLET_ATOMIC_HALT);
+    FioletHaltCause cause;
+    CHECK(fiolet_kernel_halt_cause(k, &cause));
+    CHECK(cause.reason == FIOLET_HALT_NON_FINITE);
+    fiolet_kernel_free(k);
+
+    FioletTmrKernel* t = fiolet_tmr_kernel_new_v2(config);
+    CHECK(fiolet_tmr_kernel_evaluate(t, 11.0f) == FIOLET_CONTINUE);
+    CHECK(fiolet_tmr_kernel_evaluate(t, 9.0f) == FIOLET_ATOMIC_HALT);
+    fiolet_tmr_kernel_free(t);
+
+    config.baseline = INFINITY;
+    CHECK(fiolet_config_validate_v2(config) == FIOLET_CONFIG_NON_FINITE_BASELINE);
+    config.baseline = 0.0f;
+    config.lower_limit = -1.0f;
+    CHECK(fiolet_config_validate_v2(config) == FIOLET_CONFIG_INVALID_LOWER_LIMIT);
+    config.lower_limit = 0.5f;
+    config.lower_bounded = 2;
+    CHECK(fiolet_config_validate_v2(config) == FIOLET_CONFIG_INVALID_LOWER_LIMIT);
+    k = fiolet_kernel_new_with_config_v2(config);
+    CHECK(fiolet_kernel_is_halted(k));
+    fiolet_kernel_free(k);
+}
+
+typedef struct {
+    FioletKernel* kernel;
+    int calls;
+    FioletHaltReason reason;
+    FioletDecision reentrant;
+} CallbackProbe;
+
+static void on_halt(void* context, const FioletHaltCause* cause) {
+    CallbackProbe* probe = context;
+    probe->calls++;
+    probe->reason = cause->reason;
+    /* Re-entering the latching handle must not deadlock. */
+    if (probe->kernel != NULL) {
+        probe->reentrant = fiolet_kernel_evaluate(probe->kernel, 0.0f);
+    }
+}
+
+static void check_halt_callback(void) {
+    CallbackProbe probe = { NULL, 0, 0, FIOLET_CONTINUE };
+    FioletKernel* k = fiolet_kernel_new(1.0f);
+    probe.kernel = k;
+
+    CHECK(!fiolet_kernel_set_halt_callback(k, NULL, &probe));
+    CHECK(fiolet_kernel_set_halt_callback(k, on_halt, &probe));
+    CHECK(!fiolet_kernel_set_halt_callback(k, on_halt, &probe));
+
+    CHECK(fiolet_kernel_evaluate(k, 0.5f) == FIOLET_CONTINUE);
+    CHECK(probe.calls == 0);
+    CHECK(fiolet_kernel_evaluate(k, 2.0f) == FIOLET_ATOMIC_HALT);
+    CHECK(probe.calls == 1);
+    CHECK(probe.reason == FIOLET_HALT_THRESHOLD_EXCEEDED);
+    CHECK(probe.reentrant == FIOLET_ATOMIC_HALT);
+    CHECK(fiolet_kernel_evaluate(k, NAN) == FIOLET_ATOMIC_HALT);
+    CHECK(probe.calls == 1);
+
+    /* Already halted: runs at registration, exactly once. */
+    probe.kernel = NULL;
+    CHECK(fiolet_kernel_set_halt_callback(k, on_halt, &probe));
+    CHECK(probe.calls == 2);
+    fiolet_kernel_free(k);
+
+    /* Invalid handle reads as halted. */
+    CHECK(!fiolet_kernel_set_halt_callback(k, on_halt, &probe));
+    CHECK(probe.calls == 3);
+    CHECK(probe.reason == FIOLET_HALT_INVALID_INPUT);
+}
+
+int main(void) {
+    check_version_and_provenance();
+    check_monotonic_halt();
+    check_fail_closed();
+    check_invalid_handles();
+    check_pool_exhaustion();
+    check_batch();
+    check_multi_and_fixed();
+    check_tmr();
+    check_baseline_band();
+    check_halt_callback();
+
+    if (failures != 0) {
+        fprintf(stderr, "%d check(s) failed\n", failures);
+        return 1;
+    }
+    printf("ABI conformance: OK\n");
+    return 0;
+}
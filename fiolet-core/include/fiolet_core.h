@@ -1,68 +1,709 @@
 #ifndef FIOLET_CORE_H
 #define FIOLET_CORE_H
 
+/*
+FINAL FIOLET ENGINE — SAFETY KERNEL ABI CONTRACT
+================================================
+
+This header defines the ONLY supported C ABI for the
+FINAL FIOLET no_std safety kernel.
+
+This is a SAFETY INTERLOCK, not an application API.
+
+The behavior of this kernel is FORMALLY SPECIFIED in:
+    SafetyKernel.tla
+
+That specification is the source of truth.
+
+fiolet-core/include/fiolet_core.h is an identical copy.
+*/
+
+#include <math.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+
 #ifdef __cplusplus
 extern "C" {
 #endif
 
-#include <stdint.h>
-#include <stdbool.h>
+/* ============================================================
+ * CORE TYPES
+ * ============================================================
+ */
 
 /*
- * Binary safety decision.
- * MUST remain stable (ABI contract).
- */
+FioletDecision
+--------------
+Binary decision returned by the kernel.
+
+Values are STABLE and MUST NOT be changed.
+*/
 typedef enum {
-    FIOLET_CONTINUE = 0,
+    FIOLET_CONTINUE    = 0,
     FIOLET_ATOMIC_HALT = 1
 } FioletDecision;
 
 /*
- * Opaque kernel handle.
- * Internal layout is hidden by design.
- */
+FioletSafetyConfig
+------------------
+Immutable kernel configuration. Plain data.
+Layout and meaning are frozen at ABI 1.3; the baseline band is only
+available through FioletSafetyConfigV2.
+
+Fields:
+- deviation_limit: absolute deviation threshold
+- max_steps: maximum number of evaluations (temporal horizon);
+  FIOLET_NO_STEP_BUDGET (0) disables the budget
+- window: optional K-of-N soft-limit trigger; n = 0 disables it
+- slope_limit: maximum |d_t - d_{t-1}| between consecutive deviations;
+  FIOLET_NO_SLOPE_LIMIT (+infinity) disables the rule
+*/
+#define FIOLET_NO_STEP_BUDGET 0
+#define FIOLET_WINDOW_CAPACITY 64
+#define FIOLET_NO_SLOPE_LIMIT INFINITY
+
+typedef struct {
+    float   soft_limit;
+    uint8_t k;
+    uint8_t n;
+} FioletWindowConfig;
+
+typedef struct {
+    float        deviation_limit;
+    uint64_t     max_steps;
+    FioletWindowConfig window;
+    float              slope_limit;
+} FioletSafetyConfig;
+
+/*
+FioletSafetyConfigV2
+--------------------
+FioletSafetyConfig plus a baseline band (ABI 1.4,
+FIOLET_CAP_BASELINE_BAND). Taken only by the *_v2 entry points.
+
+Fields:
+- base: as FioletSafetyConfig; base.deviation_limit is the upper
+  limit, maximum input - baseline
+- baseline: subtracted from every input before any rule applies;
+  0 evaluates inputs as precomputed deviations, any other value makes
+  the kernel baseline-relative (inputs are raw metrics)
+- lower_limit: lower limit, maximum baseline - input; applied only
+  when lower_bounded is 1
+- lower_bounded: 1 applies lower_limit, 0 leaves the band open below;
+  any other value is invalid
+
+A zero-initialized band (baseline 0, lower_bounded 0) behaves exactly
+like FioletSafetyConfig. Set lower_limit = base.deviation_limit for
+the symmetric rule |metric - baseline| > threshold.
+
+An input halts when it is non-finite, or when input - baseline is
+non-finite, above deviation_limit or (lower_bounded) below
+-lower_limit.
+*/
+typedef struct {
+    FioletSafetyConfig base;
+    float              baseline;
+    float              lower_limit;
+    uint8_t            lower_bounded;
+} FioletSafetyConfigV2;
+
+/*
+FioletKernel
+------------
+Opaque handle to a stateful safety kernel.
+
+Kernels live in a fixed, statically allocated pool inside the library
+(no heap). A handle is never dereferenced: it encodes a pool tag, a
+slot generation and a slot index, and is validated on every call.
+
+INVARIANTS (NORMATIVE):
+- Once halted, the kernel remains halted forever.
+- There exists no API to reset or override the halted state.
+- All decisions after halt are ATOMIC_HALT.
+- A NULL, freed, stale or forged handle behaves as a halted kernel:
+  every evaluation returns FIOLET_ATOMIC_HALT.
+*/
 typedef struct FioletKernel FioletKernel;
 
 /*
- * Create a new safety kernel instance.
- *
- * deviation_limit:
- *   Threshold above which the kernel irreversibly halts.
- */
-FioletKernel* fiolet_kernel_new(float deviation_limit);
-
-/*
- * Evaluate a deviation signal.
- *
- * Returns:
- *   FIOLET_CONTINUE    - safe to continue
- *   FIOLET_ATOMIC_HALT - irreversible halt
- *
- * NOTE:
- *   Once ATOMIC_HALT is returned, all future calls
- *   MUST also return ATOMIC_HALT.
+Number of kernels of each kind that may be live at once.
+Constructors return NULL when their pool is exhausted.
+*/
+#define FIOLET_MAX_KERNELS 64
+#define FIOLET_MAX_MULTI_KERNELS 16
+#define FIOLET_MAX_FIXED_KERNELS 16
+#define FIOLET_MAX_TMR_KERNELS 16
+
+/*
+FioletHaltReason
+----------------
+Why the kernel latched ATOMIC_HALT.
+Diagnostic only: NOT a decision state.
+*/
+typedef enum {
+    FIOLET_HALT_THRESHOLD_EXCEEDED = 1,
+    FIOLET_HALT_NON_FINITE         = 2,
+    FIOLET_HALT_INVALID_INPUT      = 3,
+    FIOLET_HALT_STEP_BUDGET_EXCEEDED = 4,
+    FIOLET_HALT_WINDOW_EXCEEDED    = 5,
+    FIOLET_HALT_SLOPE_EXCEEDED     = 6,
+    FIOLET_HALT_INVALID_CONFIG     = 7,
+    FIOLET_HALT_STATE_CORRUPTED    = 8,
+    FIOLET_HALT_REPLICA_DISAGREEMENT = 9,
+    FIOLET_HALT_PARENT_HALTED      = 10
+} FioletHaltReason;
+
+/*
+Channel value used when a halt is not attributed to a channel.
+*/
+#define FIOLET_NO_CHANNEL UINT32_MAX
+
+/*
+FioletHaltCause
+---------------
+Immutable record captured exactly once, at latch time.
+
+Fields:
+- reason:  why the kernel halted
+- value:   offending input (NaN if the input carried no value)
+- step:    zero-based index of the latching evaluation
+- channel: offending channel, or FIOLET_NO_CHANNEL
+*/
+typedef struct {
+    FioletHaltReason reason;
+    float            value;
+    uint64_t         step;
+    uint32_t         channel;
+} FioletHaltCause;
+
+/* ============================================================
+ * ABI FUNCTIONS
+ * ============================================================
  */
+
+/*
+fiolet_kernel_new
+-----------------
+Create a new safety kernel.
+
+Parameters:
+- limit: absolute deviation threshold
+
+Returns:
+- Handle to a kernel in Running state.
+- FAIL-CLOSED: a NaN, infinite or negative limit yields a kernel
+  that starts halted (FIOLET_HALT_INVALID_CONFIG).
+- NULL if FIOLET_MAX_KERNELS kernels are already live. A NULL handle
+  is accepted by every function and reads as halted.
+
+NOTES:
+- The kernel owns its internal state.
+- The host MUST NOT assume any internal layout.
+*/
+FioletKernel* fiolet_kernel_new(float limit);
+
+/*
+fiolet_kernel_new_with_config
+-----------------------------
+Create a new safety kernel from a full configuration.
+
+NORMATIVE BEHAVIOR (in addition to fiolet_kernel_evaluate):
+- If max_steps != FIOLET_NO_STEP_BUDGET, evaluation number
+  max_steps + 1 latches halt (FIOLET_HALT_STEP_BUDGET_EXCEEDED)
+- If window.n != 0, halt latches once window.k of the last
+  window.n deviations exceeded window.soft_limit
+  (FIOLET_HALT_WINDOW_EXCEEDED); the hard limit still applies
+- If slope_limit is finite, halt latches when |d_t - d_{t-1}| exceeds
+  it or the difference is non-finite (FIOLET_HALT_SLOPE_EXCEEDED);
+  the previous sample is held inside the kernel state
+*/
+FioletKernel* fiolet_kernel_new_with_config(FioletSafetyConfig config);
+
+/*
+fiolet_kernel_new_with_config_v2
+--------------------------------
+As fiolet_kernel_new_with_config, plus the baseline band. Evaluated
+inputs are raw metrics when a baseline is set; window and slope rules
+see input - baseline. The window counts only excursions above the
+baseline (input - baseline > window.soft_limit); drops below it are
+bounded only by lower_limit.
+*/
+FioletKernel* fiolet_kernel_new_with_config_v2(FioletSafetyConfigV2 config);
+
+/*
+FioletConfigError
+-----------------
+Reason a configuration was rejected. 0 means valid.
+*/
+typedef enum {
+    FIOLET_CONFIG_OK                   = 0,
+    FIOLET_CONFIG_NON_FINITE_LIMIT     = 1,
+    FIOLET_CONFIG_NEGATIVE_LIMIT       = 2,
+    FIOLET_CONFIG_INVALID_WINDOW       = 3,
+    FIOLET_CONFIG_INVALID_SLOPE_LIMIT  = 4,
+    FIOLET_CONFIG_INVALID_CHANNEL_COUNT = 5,
+    FIOLET_CONFIG_INVALID_DETECTOR_PARAMETER = 6,
+    FIOLET_CONFIG_UNSUPPORTED_RULE     = 7,
+    FIOLET_CONFIG_NON_FINITE_BASELINE  = 8,
+    FIOLET_CONFIG_INVALID_LOWER_LIMIT  = 9
+} FioletConfigError;
+
+/*
+fiolet_config_validate
+----------------------
+Validate a configuration without creating a kernel.
+
+Rules:
+- deviation_limit finite and >= 0
+- enabled window: 1 <= k <= n <= FIOLET_WINDOW_CAPACITY,
+  soft_limit finite and >= 0
+- slope_limit >= 0 (FIOLET_NO_SLOPE_LIMIT allowed), not NaN
+
+fiolet_config_validate_v2 additionally requires:
+- baseline finite (FIOLET_CONFIG_NON_FINITE_BASELINE)
+- lower_bounded 0 or 1; if 1, lower_limit >= 0 (+infinity allowed),
+  not NaN (FIOLET_CONFIG_INVALID_LOWER_LIMIT)
+
+Returns FIOLET_CONFIG_OK or the first violated rule.
+Every constructor applies the same rules: an invalid
+configuration yields a kernel that starts halted.
+*/
+uint32_t fiolet_config_validate(FioletSafetyConfig config);
+uint32_t fiolet_config_validate_v2(FioletSafetyConfigV2 config);
+
+/*
+fiolet_kernel_evaluate
+----------------------
+Evaluate a single deviation signal.
+
+Parameters:
+- kernel: kernel handle
+- deviation: host-provided scalar deviation (the raw metric for a
+  baseline-relative FioletSafetyConfigV2)
+
+Returns:
+- FIOLET_CONTINUE
+- FIOLET_ATOMIC_HALT
+
+NORMATIVE BEHAVIOR:
+- If the handle is invalid → always FIOLET_ATOMIC_HALT
+- If kernel is halted → always FIOLET_ATOMIC_HALT
+- If deviation > limit → latch halt + FIOLET_ATOMIC_HALT
+  (for FioletSafetyConfigV2: deviation - baseline above limit or,
+  when lower_bounded, below -lower_limit)
+- Otherwise → FIOLET_CONTINUE
+*/
 FioletDecision fiolet_kernel_evaluate(
     FioletKernel* kernel,
     float deviation
 );
 
 /*
- * Query halted state.
- *
- * Returns true iff the kernel has latched ATOMIC_HALT.
- */
+fiolet_kernel_evaluate_slice
+----------------------------
+Evaluate `len` deviations in order with ONE call across the ABI.
+
+Parameters:
+- kernel: kernel handle
+- deviations: pointer to `len` deviations
+- first_halt: optional out-parameter (may be NULL); receives the index
+  of the first element that returned FIOLET_ATOMIC_HALT, or
+  FIOLET_NO_HALT_INDEX if none did
+
+NORMATIVE BEHAVIOR:
+- Identical to calling fiolet_kernel_evaluate once per element
+- Elements after the first halting one are treated as halted
+- Returns the decision for the last element
+- NULL deviations → latch halt, first_halt = 0
+- Invalid handle → FIOLET_ATOMIC_HALT, first_halt = 0
+*/
+#define FIOLET_NO_HALT_INDEX SIZE_MAX
+
+FioletDecision fiolet_kernel_evaluate_slice(
+    FioletKernel* kernel,
+    const float* deviations,
+    size_t len,
+    size_t* first_halt
+);
+
+/*
+fiolet_kernel_is_halted
+-----------------------
+Query whether the kernel has latched into halted state.
+
+Returns:
+- true if halted or the handle is invalid
+- false otherwise
+
+NOTE:
+- This function is informational only.
+- It MUST NOT be used to gate safety logic.
+*/
 bool fiolet_kernel_is_halted(const FioletKernel* kernel);
 
 /*
- * Destroy kernel instance.
- *
- * NOTE:
- *   Optional in many safety-critical embeddings.
- *   Provided for host-side hygiene only.
- */
+fiolet_kernel_steps
+-------------------
+Number of evaluations performed while running, including the
+latching one. Frozen once halted. 0 for an invalid handle.
+Informational only.
+*/
+uint64_t fiolet_kernel_steps(const FioletKernel* kernel);
+
+/*
+fiolet_kernel_halt_cause
+------------------------
+Copy the halt cause recorded at latch time into `out`.
+
+Returns:
+- true if the kernel is halted and `out` was written
+- false if the kernel is running or `out` is NULL (`out` untouched)
+
+An invalid handle reports FIOLET_HALT_INVALID_INPUT at step 0.
+
+NOTE:
+- Informational only. Does not alter kernel state.
+*/
+bool fiolet_kernel_halt_cause(const FioletKernel* kernel, FioletHaltCause* out);
+
+/*
+FioletHaltCallback
+------------------
+Invoked exactly once when a kernel latches.
+`context` is the pointer given at registration; `cause` is valid only
+for the duration of the call.
+*/
+typedef void (*FioletHaltCallback)(void* context, const FioletHaltCause* cause);
+
+/*
+fiolet_kernel_set_halt_callback
+-------------------------------
+Register a callback invoked exactly once, synchronously, when the
+kernel latches.
+
+NORMATIVE BEHAVIOR:
+- Runs on the thread whose call latched the kernel, before that call
+  returns, AFTER the kernel is latched and the callback disarmed
+- Runs outside the handle's lock: it may call back into the same handle
+- Kernel already halted → runs before this function returns
+- The callback cannot un-halt the kernel; it must not unwind
+
+Returns:
+- true if the callback was registered
+- false if `callback` is NULL or a callback is already armed
+- false for an invalid handle, after invoking the callback immediately
+  with FIOLET_HALT_INVALID_INPUT (the handle reads as halted)
+
+NOTE:
+- fiolet_kernel_free drops an armed callback without invoking it.
+*/
+bool fiolet_kernel_set_halt_callback(
+    FioletKernel* kernel,
+    FioletHaltCallback callback,
+    void* context
+);
+
+/*
+fiolet_kernel_free
+------------------
+Release the kernel's pool slot.
+
+NOTES:
+- NULL and invalid handles are ignored.
+- The handle is dead afterwards; every later call through it behaves
+  as a halted kernel, even if the slot is reused.
+- Optional in many safety-critical embeddings.
+*/
 void fiolet_kernel_free(FioletKernel* kernel);
 
+/* ============================================================
+ * MULTI-CHANNEL KERNEL
+ * ============================================================
+ */
+
+/*
+Maximum number of channels accepted through the C ABI.
+*/
+#define FIOLET_MAX_CHANNELS 16
+
+/*
+FioletMultiKernel
+-----------------
+Opaque handle to a stateful kernel with one deviation limit per
+channel. All channels share a single monotonic latch.
+Same handle rules as FioletKernel.
+*/
+typedef struct FioletMultiKernel FioletMultiKernel;
+
+/*
+fiolet_multi_kernel_new
+-----------------------
+Create a multi-channel kernel.
+
+Parameters:
+- limits: pointer to `len` per-channel deviation limits
+- len: number of channels (1..FIOLET_MAX_CHANNELS)
+
+FAIL-CLOSED:
+- NULL limits, len == 0, len > FIOLET_MAX_CHANNELS, or any NaN,
+  infinite or negative limit → kernel starts halted
+- zero channels (len == 0) start halted with
+  FIOLET_HALT_INVALID_CONFIG: a kernel with nothing to watch never runs
+- NULL if FIOLET_MAX_MULTI_KERNELS kernels are already live
+*/
+FioletMultiKernel* fiolet_multi_kernel_new(const float* limits, size_t len);
+
+/*
+fiolet_multi_kernel_evaluate
+----------------------------
+Evaluate one deviation per channel.
+
+NORMATIVE BEHAVIOR:
+- If kernel is halted → always FIOLET_ATOMIC_HALT
+- Channels are checked in index order; the first channel whose
+  deviation is non-finite or exceeds its limit latches halt
+- NULL deviations or len != configured channels → latch halt
+- Invalid handle → FIOLET_ATOMIC_HALT
+*/
+FioletDecision fiolet_multi_kernel_evaluate(
+    FioletMultiKernel* kernel,
+    const float* deviations,
+    size_t len
+);
+
+/*
+fiolet_multi_kernel_is_halted
+-----------------------------
+Returns true if halted or the handle is invalid. Informational only.
+*/
+bool fiolet_multi_kernel_is_halted(const FioletMultiKernel* kernel);
+
+/*
+fiolet_multi_kernel_tripped_channel
+-----------------------------------
+Returns the index of the channel that latched the halt,
+or -1 if the kernel is running, halted on invalid input, or the
+handle is invalid.
+*/
+int32_t fiolet_multi_kernel_tripped_channel(const FioletMultiKernel* kernel);
+
+/*
+fiolet_multi_kernel_halt_cause
+------------------------------
+Same contract as fiolet_kernel_halt_cause.
+*/
+bool fiolet_multi_kernel_halt_cause(const FioletMultiKernel* kernel, FioletHaltCause* out);
+
+/*
+fiolet_multi_kernel_free
+------------------------
+Same contract as fiolet_kernel_free.
+*/
+void fiolet_multi_kernel_free(FioletMultiKernel* kernel);
+
+/* ============================================================
+ * FIXED-POINT KERNEL (Q16.16)
+ * ============================================================
+ */
+
+/*
+Q16.16 fixed-point value: 1.0 == FIOLET_Q16_ONE.
+Decisions are integer comparisons, bit-identical across platforms.
+*/
+typedef int32_t FioletQ16;
+
+#define FIOLET_Q16_ONE (1 << 16)
+
+/*
+FioletFixedKernel
+-----------------
+Opaque handle to a stateful Q16.16 kernel with the same invariants
+and handle rules as FioletKernel.
+*/
+typedef struct FioletFixedKernel FioletFixedKernel;
+
+/*
+fiolet_fixed_kernel_new / fiolet_fixed_kernel_new_f32
+-----------------------------------------------------
+Create a Q16.16 kernel.
+
+- _new takes the limit in Q16.16
+- _new_f32 converts the limit, rounding toward -infinity and
+  saturating
+- FAIL-CLOSED: a negative or non-finite limit yields a kernel that
+  starts halted
+- NULL if FIOLET_MAX_FIXED_KERNELS kernels are already live
+*/
+FioletFixedKernel* fiolet_fixed_kernel_new(FioletQ16 limit);
+FioletFixedKernel* fiolet_fixed_kernel_new_f32(float limit);
+
+/*
+fiolet_fixed_kernel_evaluate / fiolet_fixed_kernel_evaluate_f32
+---------------------------------------------------------------
+Evaluate a deviation.
+
+NORMATIVE BEHAVIOR:
+- If the handle is invalid → always FIOLET_ATOMIC_HALT
+- If kernel is halted → always FIOLET_ATOMIC_HALT
+- If deviation > limit → latch halt + FIOLET_ATOMIC_HALT
+- _evaluate_f32 converts the deviation, rounding toward +infinity
+  and saturating; any non-finite value latches halt
+*/
+FioletDecision fiolet_fixed_kernel_evaluate(
+    FioletFixedKernel* kernel,
+    FioletQ16 deviation
+);
+FioletDecision fiolet_fixed_kernel_evaluate_f32(
+    FioletFixedKernel* kernel,
+    float deviation
+);
+
+bool fiolet_fixed_kernel_is_halted(const FioletFixedKernel* kernel);
+bool fiolet_fixed_kernel_halt_cause(const FioletFixedKernel* kernel, FioletHaltCause* out);
+void fiolet_fixed_kernel_free(FioletFixedKernel* kernel);
+
+/* ============================================================
+ * TRIPLE-MODULAR-REDUNDANT KERNEL
+ * ============================================================
+ */
+
+/*
+FioletTmrKernel
+---------------
+Opaque handle to three independent kernel replicas built from one
+configuration. Same invariants and handle rules as FioletKernel.
+*/
+typedef struct FioletTmrKernel FioletTmrKernel;
+
+/*
+fiolet_tmr_kernel_new
+---------------------
+Create a TMR kernel. fiolet_tmr_kernel_new_v2 takes a baseline band.
+
+- FAIL-CLOSED: an invalid configuration yields a kernel that starts
+  halted (FIOLET_HALT_INVALID_CONFIG)
+- NULL if FIOLET_MAX_TMR_KERNELS kernels are already live
+*/
+FioletTmrKernel* fiolet_tmr_kernel_new(FioletSafetyConfig config);
+FioletTmrKernel* fiolet_tmr_kernel_new_v2(FioletSafetyConfigV2 config);
+
+/*
+fiolet_tmr_kernel_evaluate
+--------------------------
+Feed the deviation to all three replicas.
+
+NORMATIVE BEHAVIOR:
+- If the handle is invalid or the kernel is halted → FIOLET_ATOMIC_HALT
+- All replicas return FIOLET_CONTINUE → FIOLET_CONTINUE
+- All replicas halt → latch halt with the replicas' cause
+- Any disagreement → latch halt (FIOLET_HALT_REPLICA_DISAGREEMENT);
+  the cause's `channel` is the index (0..2) of the dissenting replica
+*/
+FioletDecision fiolet_tmr_kernel_evaluate(FioletTmrKernel* kernel, float deviation);
+
+/*
+Returns true if halted, if any replica reads as halted, or if the
+handle is invalid. Informational only.
+*/
+bool fiolet_tmr_kernel_is_halted(const FioletTmrKernel* kernel);
+bool fiolet_tmr_kernel_halt_cause(const FioletTmrKernel* kernel, FioletHaltCause* out);
+void fiolet_tmr_kernel_free(FioletTmrKernel* kernel);
+
+/* ============================================================
+ * ABI VERSION & BUILD PROVENANCE
+ * ============================================================
+ */
+
+/*
+ABI version this header describes: (major << 16) | minor.
+
+A host is compatible with the linked kernel iff
+    (fiolet_abi_version() >> 16) == FIOLET_ABI_VERSION_MAJOR
+    && (fiolet_abi_version() & 0xFFFF) >= FIOLET_ABI_VERSION_MINOR
+*/
+#define FIOLET_ABI_VERSION_MAJOR 1
+#define FIOLET_ABI_VERSION_MINOR 4
+#define FIOLET_ABI_VERSION ((FIOLET_ABI_VERSION_MAJOR << 16) | FIOLET_ABI_VERSION_MINOR)
+
+uint32_t fiolet_abi_version(void);
+
+/*
+Capability bits returned by fiolet_capabilities().
+*/
+#define FIOLET_CAP_HANDLES       (UINT64_C(1) << 0)
+#define FIOLET_CAP_CONFIG        (UINT64_C(1) << 1)
+#define FIOLET_CAP_STEP_BUDGET   (UINT64_C(1) << 2)
+#define FIOLET_CAP_WINDOW        (UINT64_C(1) << 3)
+#define FIOLET_CAP_SLOPE_LIMIT   (UINT64_C(1) << 4)
+#define FIOLET_CAP_BATCH         (UINT64_C(1) << 5)
+#define FIOLET_CAP_HALT_CAUSE    (UINT64_C(1) << 6)
+#define FIOLET_CAP_MULTI_CHANNEL (UINT64_C(1) << 7)
+#define FIOLET_CAP_FIXED_POINT   (UINT64_C(1) << 8)
+#define FIOLET_CAP_SELF_CHECK    (UINT64_C(1) << 9)
+#define FIOLET_CAP_TMR           (UINT64_C(1) << 10)
+#define FIOLET_CAP_HALT_CALLBACK (UINT64_C(1) << 11)
+#define FIOLET_CAP_BASELINE_BAND (UINT64_C(1) << 12)
+
+uint64_t fiolet_capabilities(void);
+
+/*
+Cargo feature bits reported in FioletBuildInfo.features.
+*/
+#define FIOLET_FEATURE_KERNEL (1u << 0)
+#define FIOLET_FEATURE_STD    (1u << 1)
+
+/*
+FioletBuildInfo
+---------------
+Build provenance record. Compile-time constant inside the library.
+
+Fields:
+- abi_version:  same as fiolet_abi_version()
+- features:     FIOLET_FEATURE_* bits compiled in
+- capabilities: same as fiolet_capabilities()
+- config_hash:  FNV-1a 64 over target, profile, panic strategy,
+                ABI layout and pool sizes
+- fingerprint:  FNV-1a 64 over crate version, abi_version, features,
+                capabilities and config_hash
+
+The fingerprint identifies a build. It is NOT a cryptographic
+commitment.
+*/
+typedef struct {
+    uint32_t abi_version;
+    uint32_t features;
+    uint64_t capabilities;
+    uint64_t config_hash;
+    uint64_t fingerprint;
+} FioletBuildInfo;
+
+/*
+Static NUL-terminated crate version, e.g. "0.1.0".
+*/
+const char* fiolet_crate_version(void);
+
+uint64_t fiolet_build_fingerprint(void);
+
+/*
+Copy the build provenance record into `out`.
+Returns false (and writes nothing) when `out` is NULL.
+*/
+bool fiolet_build_info(FioletBuildInfo* out);
+
+/* ============================================================
+ * EXPLICIT NON-GOALS
+ * ============================================================
+ */
+
+/*
+- No reset API
+- No serialization API
+- No heap allocation (kernels live in fixed static pools)
+- No semantic interpretation
+- No liveness guarantees
+*/
+
 #ifdef __cplusplus
 }
 #endif
@@ -0,0 +1,284 @@
+// fiolet-core/src/atomic.rs
+
+//! Lock-free safety kernel for concurrent evaluators.
+//!
+//! `AtomicSafetyKernel::evaluate` takes `&self`, so any number of threads
+//! may evaluate the same kernel without a mutex. The halt latch is one
+//! atomic word; the first thread to move it off RUNNING wins and records
+//! the cause. Every evaluation that starts after that store returns
+//! `AtomicHalt` on every thread.
+//!
+//! Linearization: an evaluation returns `Continue` only if the latch
+//! still read RUNNING after its own checks passed (a final `Acquire`
+//! load). Evaluations already in flight when another thread latches may
+//! still return `Continue`; they are ordered before the latch.
+//!
+//! Only rules that are independent of evaluation order are supported:
+//! the deviation band, non-finite input and the step budget. The K-of-N
+//! window and the slope limit need a sequential history and are rejected
+//! (`ConfigError::UnsupportedRule`, fail-closed).
+//!
+//! Requires 64-bit atomics (`target_has_atomic = "64"`).
+
+use core::cell::UnsafeCell;
+use core::hint::spin_loop;
+use core::sync::atomic::{AtomicU32, AtomicU64, Ordering};
+
+use crate::integrity::{HALTED, RUNNING};
+use crate::{config_checksum, ConfigError, HaltCause, HaltReason, SafetyConfig, SafetyDecision};
+
+/// Latch code while the winning thread records the cause.
+const LATCHING: u32 = 0x9C3A_65C5;
+
+/// Concurrently evaluable safety kernel.
+pub struct AtomicSafetyKernel {
+    config: SafetyConfig,
+    config_check: u64,
+    /// RUNNING, LATCHING or HALTED; any other value reads as halted.
+    latch: AtomicU32,
+    steps: AtomicU64,
+    /// Written once, by the thread that moved `latch` to LATCHING.
+    cause: UnsafeCell<HaltCause>,
+}
+
+// SAFETY: `cause` is written only by the single thread that wins the
+// RUNNING → LATCHING exchange, and read only after observing HALTED
+// with `Acquire`, which the writer publishes with `Release`.
+unsafe impl Sync for AtomicSafetyKernel {}
+
+impl AtomicSafetyKernel {
+    /// Create a new kernel.
+    ///
+    /// Fail-closed: an invalid configuration, or one enabling the window
+    /// or slope rule, yields a kernel that starts halted with
+    /// `HaltReason::InvalidConfig`.
+    pub const fn new(config: SafetyConfig) -> Self {
+        let valid = Self::check(&config).is_ok();
+
+        Self {
+            config,
+            config_check: config_checksum(&config),
+            latch: AtomicU32::new(if valid { RUNNING } else { HALTED }),
+            steps: AtomicU64::new(0),
+            cause: UnsafeCell::new(HaltCause::new(HaltReason::InvalidConfig, f32::NAN, 0)),
+        }
+    }
+
+    /// Create a new kernel, rejecting an invalid or unsupported configuration.
+    pub const fn try_new(config: SafetyConfig) -> Result<Self, ConfigError> {
+        match Self::check(&config) {
+            Ok(()) => Ok(Self::new(config)),
+            Err(error) => Err(error),
+        }
+    }
+
+    const fn check(config: &SafetyConfig) -> Result<(), ConfigError> {
+        if let Err(error) = config.validate() {
+            return Err(error);
+        }
+        if config.window.is_enabled() || config.has_slope_limit() {
+            return Err(ConfigError::UnsupportedRule);
+        }
+        Ok(())
+    }
+
+    /// Evaluate a deviation. Callable from any thread.
+    pub fn evaluate(&self, deviation: f32) -> SafetyDecision {
+        if self.latch.load(Ordering::Acquire) != RUNNING {
+            return SafetyDecision::AtomicHalt;
+        }
+
+        if self.config_check != config_checksum(&self.config) {
+            let step = self.steps.load(Ordering::Relaxed);
+            return self.latch(HaltCause::new(HaltReason::StateCorrupted, f32::NAN, step));
+        }
+
+        let step = match self
+            .steps
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |steps| {
+                Some(steps.saturating_add(1))
+            }) {
+            Ok(step) | Err(step) => step,
+        };
+
+        let budget = self.config.max_steps;
+        if budget != SafetyConfig::NO_STEP_BUDGET && step >= budget {
+            return self.latch(HaltCause::new(
+                HaltReason::StepBudgetExceeded,
+                deviation,
+                step,
+            ));
+        }
+
+        if let Err(reason) = self.config.deviation(deviation) {
+            return self.latch(HaltCause::new(reason, deviation, step));
+        }
+
+        // Linearization point: a halt latched meanwhile wins.
+        if self.latch.load(Ordering::Acquire) != RUNNING {
+            return SafetyDecision::AtomicHalt;
+        }
+        SafetyDecision::Continue
+    }
+
+    /// Latch halt, recording its cause. The first cause is never replaced.
+    fn latch(&self, cause: HaltCause) -> SafetyDecision {
+        if self
+            .latch
+            .compare_exchange(RUNNING, LATCHING, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+        {
+            // SAFETY: this thread alone won RUNNING → LATCHING; readers
+            // wait for HALTED.
+            unsafe { *self.cause.get() = cause };
+            self.latch.store(HALTED, Ordering::Release);
+        }
+        SafetyDecision::AtomicHalt
+    }
+
+    /// Query whether the kernel is halted (or latching). Wait-free.
+    pub fn is_halted(&self) -> bool {
+        self.latch.load(Ordering::Acquire) != RUNNING
+    }
+
+    /// Cause recorded at latch time, or `None` while running.
+    ///
+    /// May spin briefly while another thread is recording the cause.
+    /// A corrupted latch word reports `StateCorrupted`.
+    pub fn halt_cause(&self) -> Option<HaltCause> {
+        loop {
+            match self.latch.load(Ordering::Acquire) {
+                RUNNING => return None,
+                LATCHING => spin_loop(),
+                // SAFETY: HALTED was published with `Release` after the
+                // single write to `cause`.
+                HALTED => return Some(unsafe { *self.cause.get() }),
+                _ => {
+                    return Some(HaltCause::new(
+                        HaltReason::StateCorrupted,
+                        f32::NAN,
+                        self.steps(),
+                    ))
+                }
+            }
+        }
+    }
+
+    /// Evaluations started while running (including latching ones).
+    pub fn steps(&self) -> u64 {
+        self.steps.load(Ordering::Relaxed)
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+    use std::thread;
+
+    #[test]
+    fn sequential_use_matches_safety_kernel() {
+        let kernel = AtomicSafetyKernel::new(SafetyConfig::new(0.5));
+
+        assert_eq!(kernel.evaluate(0.5), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(0.75), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::ThresholdExceeded);
+        assert_eq!(cause.step(), 1);
+        assert_eq!(kernel.steps(), 2);
+    }
+
+    #[test]
+    fn order_dependent_rules_are_rejected() {
+        let windowed = SafetyConfig::new(1.0).with_window(0.5, 1, 2);
+        let sloped = SafetyConfig::new(1.0).with_slope_limit(0.1);
+
+        for config in [windowed, sloped] {
+            assert_eq!(
+                AtomicSafetyKernel::try_new(config).err(),
+                Some(ConfigError::UnsupportedRule)
+            );
+            let kernel = AtomicSafetyKernel::new(config);
+            assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+            assert_eq!(
+                kernel.halt_cause().unwrap().reason(),
+                HaltReason::InvalidConfig
+            );
+        }
+    }
+
+    #[test]
+    fn baseline_band_is_supported() {
+        let kernel = AtomicSafetyKernel::new(SafetyConfig::relative(10.0, 1.0).with_band(0.5, 2.0));
+
+        assert_eq!(kernel.evaluate(12.0), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(9.5), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(9.25), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::ThresholdExceeded
+        );
+    }
+
+    #[test]
+    fn halt_is_observed_by_every_thread() {
+        let kernel = AtomicSafetyKernel::new(SafetyConfig::new(1.0));
+
+        thread::scope(|scope| {
+            for worker in 0..8 {
+                let kernel = &kernel;
+                scope.spawn(move || {
+                    let mut seen_halt = false;
+                    for i in 0..2_000 {
+                        let deviation = if worker == 3 && i == 500 { 2.0 } else { 0.5 };
+                        let decision = kernel.evaluate(deviation);
+
+                        // Per observer: no Continue after a halt.
+                        assert!(!(seen_halt && decision == SafetyDecision::Continue));
+                        seen_halt |= decision == SafetyDecision::AtomicHalt;
+                    }
+                    assert!(seen_halt || worker != 3);
+                });
+            }
+        });
+
+        assert!(kernel.is_halted());
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::ThresholdExceeded
+        );
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+    }
+
+    #[test]
+    fn step_budget_holds_under_contention() {
+        let kernel = AtomicSafetyKernel::new(SafetyConfig::new(1.0).with_max_steps(1_000));
+        let continues = AtomicUsize::new(0);
+
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..500 {
+                        if kernel.evaluate(0.0) == SafetyDecision::Continue {
+                            continues.fetch_add(1, Ordering::Relaxed);
+                        }
+                    }
+                });
+            }
+        });
+
+        assert!(continues.load(Ordering::Relaxed) <= 1_000);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::StepBudgetExceeded
+        );
+        // Racing threads past the budget may latch with any step >= budget.
+        assert!(kernel.halt_cause().unwrap().step() >= 1_000);
+    }
+}
@@ -0,0 +1,304 @@
+// fiolet-core/src/callback.rs
+
+//! Exactly-once halt callbacks.
+//!
+//! A host may register one callback per `SafetyKernel`. It is invoked
+//! synchronously, exactly once, right after the kernel latches (or at
+//! registration, if the kernel is already halted), so outputs can be
+//! zeroed and other components signalled without polling.
+//!
+//! The callback can never un-halt the kernel:
+//!
+//! - the latch is written BEFORE the callback runs;
+//! - the callback is disarmed BEFORE it runs, so re-entrant calls and
+//!   panics cannot make it fire twice;
+//! - it only receives a copy of the cause, never the kernel.
+//!
+//! Behind the C ABI the callback runs after the handle's slot lock is
+//! released, so it may call back into the same handle. Under `std`,
+//! `NotifyingKernel` offers the same contract for a Rust closure.
+//!
+//! Callbacks must not panic. The workspace builds with `panic = "abort"`,
+//! where a panicking callback aborts the process. `NotifyingKernel` wraps
+//! the closure in `catch_unwind`, which only contains the panic when the
+//! crate is built with `panic = "unwind"`.
+//!
+//! The registration carries its own checksum; a corrupted callback
+//! pointer is never called.
+
+use core::ffi::c_void;
+
+use crate::integrity::{fnv1a_start, fnv1a_u64};
+use crate::HaltCause;
+
+/// Halt callback: `context` as registered, `cause` valid for the call only.
+pub type FioletHaltCallback = extern "C" fn(context: *mut c_void, cause: *const HaltCause);
+
+/// Registered callback, self-checking.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub(crate) struct HaltHook {
+    callback: Option<FioletHaltCallback>,
+    /// Opaque to the kernel; stored as an address so kernels stay `Send`.
+    context: usize,
+    check: u64,
+}
+
+impl HaltHook {
+    /// No callback registered.
+    pub(crate) const NONE: Self = Self {
+        callback: None,
+        context: 0,
+        check: hook_checksum(0, 0),
+    };
+
+    pub(crate) fn new(callback: FioletHaltCallback, context: *mut c_void) -> Self {
+        Self {
+            callback: Some(callback),
+            context: context.expose_provenance(),
+            check: hook_checksum(callback as usize, context.addr()),
+        }
+    }
+
+    /// A callback is registered and has not fired.
+    pub(crate) const fn is_armed(&self) -> bool {
+        self.callback.is_some()
+    }
+
+    /// Disarm, returning the registration exactly once.
+    pub(crate) fn take(&mut self) -> Option<Self> {
+        if !self.is_armed() {
+            return None;
+        }
+        let hook = *self;
+        *self = Self::NONE;
+        Some(hook)
+    }
+
+    fn is_intact(&self) -> bool {
+        let callback = self.callback.map_or(0, |callback| callback as usize);
+        self.check == hook_checksum(callback, self.context)
+    }
+}
+
+const fn hook_checksum(callback: usize, context: usize) -> u64 {
+    let hash = fnv1a_start(b"fiolet-halt-hook");
+    fnv1a_u64(fnv1a_u64(hash, callback as u64), context as u64)
+}
+
+/// Disarmed callback and the cause to deliver, detached from the kernel
+/// so it can be invoked after any lock on the kernel is released.
+#[must_use = "the halt callback only runs when fired"]
+pub(crate) struct PendingCallback {
+    hook: HaltHook,
+    cause: HaltCause,
+}
+
+impl PendingCallback {
+    pub(crate) const fn new(hook: HaltHook, cause: HaltCause) -> Self {
+        Self { hook, cause }
+    }
+
+    /// Invoke the callback. A corrupted registration is dropped silently:
+    /// the kernel is halted either way.
+    pub(crate) fn fire(self) {
+        if !self.hook.is_intact() {
+            return;
+        }
+        if let Some(callback) = self.hook.callback {
+            callback(
+                core::ptr::with_exposed_provenance_mut(self.hook.context),
+                &self.cause,
+            );
+        }
+    }
+}
+
+// ============================================================
+// RUST CLOSURES (STD ONLY)
+// ============================================================
+
+#[cfg(feature = "std")]
+pub use notifying::NotifyingKernel;
+
+#[cfg(feature = "std")]
+mod notifying {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    use crate::{BatchOutcome, HaltCause, SafetyConfig, SafetyDecision, SafetyKernel, Verdict};
+
+    /// `SafetyKernel` that runs a Rust closure exactly once when it halts.
+    ///
+    /// The closure must not panic: under `panic = "abort"` (the workspace
+    /// profile) a panic aborts the process. Only under `panic = "unwind"`
+    /// is it contained, with the kernel still halted.
+    pub struct NotifyingKernel<F: FnOnce(&HaltCause)> {
+        kernel: SafetyKernel,
+        on_halt: Option<F>,
+    }
+
+    impl<F: FnOnce(&HaltCause)> NotifyingKernel<F> {
+        /// Wrap a new kernel; fail-closed like `SafetyKernel::new`.
+        pub fn new(config: SafetyConfig) -> Self {
+            Self {
+                kernel: SafetyKernel::new(config),
+                on_halt: None,
+            }
+        }
+
+        /// Register `on_halt`. Runs immediately if the kernel is already
+        /// halted. Returns false (dropping `on_halt`) if a closure is
+        /// already registered.
+        pub fn on_halt(&mut self, on_halt: F) -> bool {
+            if self.on_halt.is_some() {
+                return false;
+            }
+            self.on_halt = Some(on_halt);
+            self.notify();
+            true
+        }
+
+        /// Evaluate a deviation; see `SafetyKernel::evaluate`.
+        pub fn evaluate(&mut self, deviation: f32) -> Verdict {
+            let verdict = self.kernel.evaluate(deviation);
+            if verdict == SafetyDecision::AtomicHalt {
+                self.notify();
+            }
+            verdict
+        }
+
+        /// Evaluate deviations in order; see `SafetyKernel::evaluate_slice`.
+        pub fn evaluate_slice(&mut self, deviations: &[f32]) -> BatchOutcome {
+            let outcome = self.kernel.evaluate_slice(deviations);
+            if outcome.decision == SafetyDecision::AtomicHalt {
+                self.notify();
+            }
+            outcome
+        }
+
+        /// Query whether the kernel is halted.
+        pub const fn is_halted(&self) -> bool {
+            self.kernel.is_halted()
+        }
+
+        /// Cause recorded at latch time, or `None` while running.
+        pub const fn halt_cause(&self) -> Option<HaltCause> {
+            self.kernel.halt_cause()
+        }
+
+        /// Number of evaluations performed while running.
+        pub const fn steps(&self) -> u64 {
+            self.kernel.steps()
+        }
+
+        fn notify(&mut self) {
+            let Some(cause) = self.kernel.halt_cause() else {
+                return;
+            };
+            if let Some(on_halt) = self.on_halt.take() {
+                // Already disarmed and latched: a panic changes nothing.
+                // Effective under `panic = "unwind"` only; under "abort"
+                // the process ends before this returns.
+                let _ = catch_unwind(AssertUnwindSafe(|| on_halt(&cause)));
+            }
+        }
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HaltReason, SafetyConfig, SafetyDecision, SafetyKernel};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    extern "C" fn count(context: *mut c_void, cause: *const HaltCause) {
+        // SAFETY: every test registers a live `AtomicU32` as context.
+        let counter = unsafe { &*(context as *const AtomicU32) };
+        // SAFETY: the kernel passes a cause valid for the call.
+        let cause = unsafe { &*cause };
+        counter.fetch_add(cause.reason() as u32 * 100 + 1, Ordering::SeqCst);
+    }
+
+    fn context(counter: &AtomicU32) -> *mut c_void {
+        counter as *const AtomicU32 as *mut c_void
+    }
+
+    #[test]
+    fn callback_fires_exactly_once_at_latch() {
+        let calls = AtomicU32::new(0);
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(1.0));
+        assert!(kernel.set_halt_callback(count, context(&calls)));
+        assert!(!kernel.set_halt_callback(count, context(&calls)));
+
+        assert_eq!(kernel.evaluate(0.5), SafetyDecision::Continue);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+
+        assert_eq!(kernel.evaluate(2.0), SafetyDecision::AtomicHalt);
+        assert_eq!(calls.load(Ordering::SeqCst), 101);
+
+        kernel.evaluate(f32::NAN);
+        kernel.evaluate_slice(&[0.0, 3.0]);
+        assert_eq!(calls.load(Ordering::SeqCst), 101);
+    }
+
+    #[test]
+    fn callback_on_halted_kernel_fires_at_registration() {
+        let calls = AtomicU32::new(0);
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(f32::NAN));
+
+        assert!(kernel.set_halt_callback(count, context(&calls)));
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            HaltReason::InvalidConfig as u32 * 100 + 1
+        );
+        kernel.evaluate(0.0);
+        assert_eq!(
+            calls.load(Ordering::SeqCst),
+            HaltReason::InvalidConfig as u32 * 100 + 1
+        );
+    }
+
+    #[test]
+    fn corrupted_registration_is_never_called() {
+        let calls = AtomicU32::new(0);
+        let mut hook = HaltHook::new(count, context(&calls));
+        hook.context ^= 1 << 3;
+
+        let cause = HaltCause::new(HaltReason::ThresholdExceeded, 2.0, 0);
+        PendingCallback::new(hook, cause).fire();
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+    }
+
+    // Test builds unwind regardless of the profile's `panic = "abort"`, so
+    // this covers `panic = "unwind"` builds only; see the module docs.
+    #[cfg(feature = "std")]
+    #[test]
+    fn panicking_closure_cannot_unhalt_under_unwind() {
+        let mut kernel = NotifyingKernel::new(SafetyConfig::new(1.0));
+        assert!(kernel.on_halt(|_: &HaltCause| panic!("misbehaving host")));
+
+        assert_eq!(kernel.evaluate(2.0), SafetyDecision::AtomicHalt);
+        assert!(kernel.is_halted());
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::ThresholdExceeded
+        );
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn closure_fires_exactly_once() {
+        let calls = std::cell::Cell::new(0);
+        let mut kernel = NotifyingKernel::new(SafetyConfig::new(1.0));
+        assert!(kernel.on_halt(|cause: &HaltCause| calls.set(calls.get() + cause.step() + 1)));
+
+        kernel.evaluate_slice(&[0.1, 0.2, 5.0, 0.3]);
+        kernel.evaluate(f32::INFINITY);
+        assert_eq!(calls.get(), 3);
+    }
+}
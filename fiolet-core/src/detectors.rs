@@ -0,0 +1,343 @@
+// fiolet-core/src/detectors.rs
+
+//! Sequential change detectors (CUSUM, EWMA, Page-Hinkley).
+//!
+//! Slow drift never crosses a per-step threshold. These detectors fold
+//! the deviation stream into a single statistic that grows under drift;
+//! `DetectorKernel` feeds that statistic to a latching `SafetyKernel`.
+//!
+//! All parameters are fixed at construction. Updates use plain IEEE-754
+//! `f32` arithmetic only (no fused operations), so identical inputs give
+//! identical statistics (ABI_CONTRACT.md §6). A non-finite input yields
+//! a non-finite statistic, which the kernel treats as unsafe.
+
+use crate::{ConfigError, HaltCause, SafetyConfig, SafetyKernel, Validate, Verdict};
+
+/// Finite and non-negative.
+fn check_non_negative(value: f32) -> Result<(), ConfigError> {
+    if value.is_finite() && value >= 0.0 {
+        Ok(())
+    } else {
+        Err(ConfigError::InvalidDetectorParameter)
+    }
+}
+
+/// Finite.
+fn check_finite(value: f32) -> Result<(), ConfigError> {
+    if value.is_finite() {
+        Ok(())
+    } else {
+        Err(ConfigError::InvalidDetectorParameter)
+    }
+}
+
+/// A deterministic, allocation-free sequential change detector.
+///
+/// Parameters are validated through `Validate`; a `DetectorKernel` built
+/// around an invalid detector starts halted.
+pub trait ChangeDetector: Validate {
+    /// Fold one sample into the detector and return the new statistic.
+    fn update(&mut self, sample: f32) -> f32;
+
+    /// Current statistic without consuming a sample.
+    fn statistic(&self) -> f32;
+}
+
+/// One-sided (upper) CUSUM.
+///
+/// `S_t = max(0, S_{t−1} + x_t − target − slack)`
+#[derive(Copy, Clone, Debug)]
+pub struct Cusum {
+    target: f32,
+    slack: f32,
+    sum: f32,
+}
+
+impl Cusum {
+    /// Create a CUSUM around `target` with allowance `slack`.
+    pub const fn new(target: f32, slack: f32) -> Self {
+        Self {
+            target,
+            slack,
+            sum: 0.0,
+        }
+    }
+}
+
+impl Validate for Cusum {
+    /// `target` finite, `slack` finite and non-negative.
+    fn validate(&self) -> Result<(), ConfigError> {
+        check_finite(self.target)?;
+        check_non_negative(self.slack)
+    }
+}
+
+impl ChangeDetector for Cusum {
+    fn update(&mut self, sample: f32) -> f32 {
+        let next = self.sum + (sample - self.target - self.slack);
+
+        // `f32::max` would swallow NaN; keep non-finite values visible.
+        self.sum = if !next.is_finite() || next > 0.0 {
+            next
+        } else {
+            0.0
+        };
+        self.sum
+    }
+
+    fn statistic(&self) -> f32 {
+        self.sum
+    }
+}
+
+/// Exponentially weighted moving average with frozen smoothing.
+///
+/// `z_t = λ·x_t + (1 − λ)·z_{t−1}`, statistic `|z_t − target|`.
+#[derive(Copy, Clone, Debug)]
+pub struct Ewma {
+    lambda: f32,
+    target: f32,
+    average: f32,
+}
+
+impl Ewma {
+    /// Create an EWMA starting at `target` with smoothing `lambda`.
+    pub const fn new(target: f32, lambda: f32) -> Self {
+        Self {
+            lambda,
+            target,
+            average: target,
+        }
+    }
+}
+
+impl Validate for Ewma {
+    /// `target` finite, `0 < lambda <= 1`.
+    fn validate(&self) -> Result<(), ConfigError> {
+        check_finite(self.target)?;
+        if self.lambda > 0.0 && self.lambda <= 1.0 {
+            Ok(())
+        } else {
+            Err(ConfigError::InvalidDetectorParameter)
+        }
+    }
+}
+
+impl ChangeDetector for Ewma {
+    fn update(&mut self, sample: f32) -> f32 {
+        self.average = self.lambda * sample + (1.0 - self.lambda) * self.average;
+        self.statistic()
+    }
+
+    fn statistic(&self) -> f32 {
+        (self.average - self.target).abs()
+    }
+}
+
+/// Page-Hinkley test for an upward shift in the mean.
+///
+/// `m_t = Σ (x_i − x̄_i − δ)`, statistic `m_t − min_{i≤t} m_i`.
+#[derive(Copy, Clone, Debug)]
+pub struct PageHinkley {
+    delta: f32,
+    count: u32,
+    mean: f32,
+    cumulative: f32,
+    minimum: f32,
+}
+
+impl PageHinkley {
+    /// Create a Page-Hinkley detector with drift allowance `delta`.
+    pub const fn new(delta: f32) -> Self {
+        Self {
+            delta,
+            count: 0,
+            mean: 0.0,
+            cumulative: 0.0,
+            minimum: 0.0,
+        }
+    }
+}
+
+impl Validate for PageHinkley {
+    /// `delta` finite and non-negative.
+    fn validate(&self) -> Result<(), ConfigError> {
+        check_non_negative(self.delta)
+    }
+}
+
+impl ChangeDetector for PageHinkley {
+    fn update(&mut self, sample: f32) -> f32 {
+        self.count = self.count.saturating_add(1);
+        self.mean += (sample - self.mean) / self.count as f32;
+        self.cumulative += sample - self.mean - self.delta;
+
+        if self.cumulative < self.minimum {
+            self.minimum = self.cumulative;
+        }
+        self.statistic()
+    }
+
+    fn statistic(&self) -> f32 {
+        self.cumulative - self.minimum
+    }
+}
+
+/// A change detector whose statistic feeds a latching `SafetyKernel`.
+///
+/// The kernel's `deviation_limit` bounds the detector statistic.
+/// Once halted, the detector state is frozen.
+pub struct DetectorKernel<D: ChangeDetector> {
+    detector: D,
+    kernel: SafetyKernel,
+}
+
+impl<D: ChangeDetector> DetectorKernel<D> {
+    /// Combine a detector with a kernel configuration.
+    ///
+    /// Fail-closed: an invalid detector or configuration yields a kernel
+    /// that starts halted.
+    pub fn new(detector: D, config: SafetyConfig) -> Self {
+        let mut kernel = SafetyKernel::new(config);
+        if detector.validate().is_err() {
+            kernel.latch_invalid_config();
+        }
+
+        Self { detector, kernel }
+    }
+
+    /// Combine a detector with a kernel configuration, rejecting either
+    /// if invalid.
+    pub fn try_new(detector: D, config: SafetyConfig) -> Result<Self, ConfigError> {
+        detector.validate()?;
+        config.validate()?;
+        Ok(Self::new(detector, config))
+    }
+
+    /// Fold one sample into the detector and evaluate its statistic.
+    pub fn evaluate(&mut self, sample: f32) -> Verdict {
+        if self.kernel.is_halted() {
+            return Verdict::AtomicHalt;
+        }
+
+        let statistic = self.detector.update(sample);
+        self.kernel.evaluate(statistic)
+    }
+
+    /// Query whether the kernel is already halted.
+    pub const fn is_halted(&self) -> bool {
+        self.kernel.is_halted()
+    }
+
+    /// Cause recorded at latch time, or `None` while running.
+    pub const fn halt_cause(&self) -> Option<HaltCause> {
+        self.kernel.halt_cause()
+    }
+
+    /// Detector statistic after the last evaluated sample.
+    pub fn statistic(&self) -> f32 {
+        self.detector.statistic()
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HaltReason, SafetyDecision};
+
+    #[test]
+    fn cusum_detects_slow_drift() {
+        let mut kernel = DetectorKernel::new(Cusum::new(0.0, 0.25), SafetyConfig::new(1.0));
+
+        // Every sample is far below the limit, but the drift accumulates.
+        let mut halted_at = None;
+        for step in 0..100 {
+            if kernel.evaluate(0.5) == SafetyDecision::AtomicHalt {
+                halted_at = Some(step);
+                break;
+            }
+        }
+
+        assert_eq!(halted_at, Some(4));
+        assert_eq!(kernel.statistic(), 1.25);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::ThresholdExceeded
+        );
+    }
+
+    #[test]
+    fn cusum_resets_at_zero() {
+        let mut cusum = Cusum::new(0.0, 0.1);
+
+        assert_eq!(cusum.update(-5.0), 0.0);
+        assert_eq!(cusum.update(0.1), 0.0);
+    }
+
+    #[test]
+    fn ewma_tracks_shift_from_target() {
+        let mut ewma = Ewma::new(1.0, 0.5);
+
+        assert_eq!(ewma.update(1.0), 0.0);
+        assert_eq!(ewma.update(3.0), 1.0);
+        assert_eq!(ewma.update(3.0), 1.5);
+    }
+
+    #[test]
+    fn page_hinkley_flags_mean_shift() {
+        let mut kernel = DetectorKernel::new(PageHinkley::new(0.01), SafetyConfig::new(2.0));
+
+        for _ in 0..50 {
+            assert_eq!(kernel.evaluate(0.1), SafetyDecision::Continue);
+        }
+
+        let mut halted = false;
+        for _ in 0..50 {
+            if kernel.evaluate(0.6) == SafetyDecision::AtomicHalt {
+                halted = true;
+                break;
+            }
+        }
+        assert!(halted);
+    }
+
+    #[test]
+    fn non_finite_sample_halts_every_detector() {
+        let config = SafetyConfig::new(1.0);
+
+        let mut cusum = DetectorKernel::new(Cusum::new(0.0, 0.0), config);
+        let mut ewma = DetectorKernel::new(Ewma::new(0.0, 0.2), config);
+        let mut ph = DetectorKernel::new(PageHinkley::new(0.0), config);
+
+        assert_eq!(cusum.evaluate(f32::NAN), SafetyDecision::AtomicHalt);
+        assert_eq!(ewma.evaluate(f32::INFINITY), SafetyDecision::AtomicHalt);
+        assert_eq!(ph.evaluate(f32::NEG_INFINITY), SafetyDecision::AtomicHalt);
+
+        assert_eq!(cusum.halt_cause().unwrap().reason(), HaltReason::NonFinite);
+    }
+
+    #[test]
+    fn invalid_parameters_start_halted() {
+        let config = SafetyConfig::new(1.0);
+
+        assert!(DetectorKernel::new(Ewma::new(0.0, 0.0), config).is_halted());
+        assert!(DetectorKernel::new(Cusum::new(f32::NAN, 0.1), config).is_halted());
+        assert!(DetectorKernel::new(PageHinkley::new(-0.1), config).is_halted());
+        assert!(DetectorKernel::new(Ewma::new(0.0, 0.5), SafetyConfig::new(-1.0)).is_halted());
+
+        assert_eq!(
+            DetectorKernel::try_new(Ewma::new(0.0, 1.5), config).err(),
+            Some(ConfigError::InvalidDetectorParameter)
+        );
+
+        let kernel = DetectorKernel::new(Ewma::new(0.0, 0.0), config);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::InvalidConfig
+        );
+    }
+}
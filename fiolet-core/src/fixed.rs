@@ -0,0 +1,262 @@
+// fiolet-core/src/fixed.rs
+
+//! Q16.16 fixed-point safety kernel.
+//!
+//! Same invariants (I1–I6) as the `f32` kernel, but the comparison runs on
+//! integers, so decisions are bit-identical across platforms
+//! (KNOWN_FAILURE_MODES.md §6). Mirrors the Q16.16 convention of
+//! `src/value_manifold.rs`.
+//!
+//! Conversion from `f32` is well-defined and fail-closed:
+//! - non-finite input has no Q16.16 value and latches halt
+//! - out-of-range input saturates to `Q16_MAX` / `Q16_MIN`
+//! - deviations round toward +∞, limits round toward −∞,
+//!   so quantization never turns a halt into a continue
+
+use crate::{ConfigError, HaltCause, HaltReason, SafetyDecision};
+
+/// Q16.16 fixed-point value.
+pub type Q16 = i32;
+
+/// Fractional bits of `Q16`.
+pub const FP_SHIFT: u32 = 16;
+
+/// 1.0 in Q16.16.
+pub const Q16_ONE: Q16 = 1 << FP_SHIFT;
+
+/// Largest representable Q16.16 value (≈ 32767.99998).
+pub const Q16_MAX: Q16 = i32::MAX;
+
+/// Smallest representable Q16.16 value (−32768.0).
+pub const Q16_MIN: Q16 = i32::MIN;
+
+const SCALE: f32 = Q16_ONE as f32;
+
+/// Convert a deviation to Q16.16, rounding toward +∞.
+///
+/// Returns `None` for non-finite input. Saturates out-of-range values.
+pub fn q16_from_f32(value: f32) -> Option<Q16> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    let scaled = value * SCALE;
+    // `as` truncates toward zero and saturates at the i32 bounds.
+    let truncated = scaled as Q16;
+    if (truncated as f32) < scaled {
+        Some(truncated.saturating_add(1))
+    } else {
+        Some(truncated)
+    }
+}
+
+/// Convert a limit to Q16.16, rounding toward −∞.
+///
+/// Returns `None` for non-finite input. Saturates out-of-range values.
+pub fn q16_limit_from_f32(value: f32) -> Option<Q16> {
+    if !value.is_finite() {
+        return None;
+    }
+
+    let scaled = value * SCALE;
+    let truncated = scaled as Q16;
+    if (truncated as f32) > scaled {
+        Some(truncated.saturating_sub(1))
+    } else {
+        Some(truncated)
+    }
+}
+
+/// Convert a Q16.16 value to `f32` (diagnostics only).
+pub const fn q16_to_f32(value: Q16) -> f32 {
+    value as f32 / SCALE
+}
+
+/// Fixed-point safety kernel state.
+///
+/// Minimal, deterministic, monotonic.
+#[repr(C)]
+pub struct FixedSafetyKernel {
+    limit: Q16,
+    halted: bool,
+    steps: u64,
+    cause: HaltCause,
+}
+
+impl FixedSafetyKernel {
+    /// Create a new kernel with a Q16.16 deviation limit.
+    ///
+    /// Fail-closed: a negative limit yields a kernel that starts halted.
+    pub const fn new(limit: Q16) -> Self {
+        Self {
+            limit,
+            halted: limit < 0,
+            steps: 0,
+            // Only observable once `halted` is set.
+            cause: HaltCause::new(HaltReason::InvalidConfig, q16_to_f32(limit), 0),
+        }
+    }
+
+    /// Create a new kernel, rejecting a negative limit.
+    pub const fn try_new(limit: Q16) -> Result<Self, ConfigError> {
+        if limit < 0 {
+            Err(ConfigError::NegativeLimit)
+        } else {
+            Ok(Self::new(limit))
+        }
+    }
+
+    /// Create a new kernel from an `f32` limit.
+    ///
+    /// Fail-closed: a non-finite or negative limit yields an
+    /// already-halted kernel.
+    pub fn from_f32_limit(limit: f32) -> Self {
+        match q16_limit_from_f32(limit) {
+            Some(limit) => Self::new(limit),
+            None => {
+                let mut kernel = Self::new(0);
+                kernel.latch(HaltCause::new(HaltReason::InvalidConfig, limit, 0));
+                kernel
+            }
+        }
+    }
+
+    /// Evaluate a single Q16.16 deviation signal.
+    pub fn evaluate(&mut self, deviation: Q16) -> SafetyDecision {
+        if self.halted {
+            return SafetyDecision::AtomicHalt;
+        }
+
+        let step = self.next_step();
+
+        if deviation > self.limit {
+            self.latch(HaltCause::new(
+                HaltReason::ThresholdExceeded,
+                q16_to_f32(deviation),
+                step,
+            ))
+        } else {
+            SafetyDecision::Continue
+        }
+    }
+
+    /// Convert an `f32` deviation and evaluate it.
+    ///
+    /// Non-finite input latches halt.
+    pub fn evaluate_f32(&mut self, deviation: f32) -> SafetyDecision {
+        if self.halted {
+            return SafetyDecision::AtomicHalt;
+        }
+
+        match q16_from_f32(deviation) {
+            Some(deviation) => self.evaluate(deviation),
+            None => {
+                let step = self.next_step();
+                self.latch(HaltCause::new(HaltReason::NonFinite, deviation, step))
+            }
+        }
+    }
+
+    fn next_step(&mut self) -> u64 {
+        let step = self.steps;
+        self.steps = self.steps.saturating_add(1);
+        step
+    }
+
+    /// Latch halt, recording its cause. The first cause is never replaced.
+    fn latch(&mut self, cause: HaltCause) -> SafetyDecision {
+        if !self.halted {
+            self.halted = true;
+            self.cause = cause;
+        }
+        SafetyDecision::AtomicHalt
+    }
+
+    /// Query whether the kernel is already halted.
+    pub const fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Cause recorded at latch time, or `None` while running.
+    pub const fn halt_cause(&self) -> Option<HaltCause> {
+        if self.halted {
+            Some(self.cause)
+        } else {
+            None
+        }
+    }
+
+    /// Configured Q16.16 limit.
+    pub const fn limit(&self) -> Q16 {
+        self.limit
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fixed_halt_is_irreversible() {
+        let mut kernel = FixedSafetyKernel::new(Q16_ONE / 2);
+
+        assert_eq!(kernel.evaluate(Q16_ONE / 10), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(Q16_ONE / 2), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(Q16_ONE / 2 + 1), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.evaluate(Q16_MIN), SafetyDecision::AtomicHalt);
+        assert!(kernel.is_halted());
+    }
+
+    #[test]
+    fn conversion_is_saturating_and_fail_closed() {
+        assert_eq!(q16_from_f32(1.0), Some(Q16_ONE));
+        assert_eq!(q16_from_f32(1e9), Some(Q16_MAX));
+        assert_eq!(q16_from_f32(-1e9), Some(Q16_MIN));
+        assert_eq!(q16_from_f32(f32::NAN), None);
+        assert_eq!(q16_from_f32(f32::INFINITY), None);
+
+        // Deviations round up, limits round down.
+        assert_eq!(q16_from_f32(1.0e-6), Some(1));
+        assert_eq!(q16_limit_from_f32(1.0e-6), Some(0));
+        assert_eq!(q16_from_f32(-1.0e-6), Some(0));
+        assert_eq!(q16_limit_from_f32(-1.0e-6), Some(-1));
+    }
+
+    #[test]
+    fn quantization_never_hides_a_halt() {
+        let mut kernel = FixedSafetyKernel::from_f32_limit(1.0);
+
+        assert_eq!(kernel.evaluate_f32(1.0), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate_f32(1.000001), SafetyDecision::AtomicHalt);
+    }
+
+    #[test]
+    fn non_finite_input_latches_halt() {
+        let mut kernel = FixedSafetyKernel::from_f32_limit(1.0);
+
+        assert_eq!(kernel.evaluate_f32(f32::NAN), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.evaluate(0), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.halt_cause().unwrap().reason(), HaltReason::NonFinite);
+
+        let invalid = FixedSafetyKernel::from_f32_limit(f32::INFINITY);
+        assert!(invalid.is_halted());
+        assert_eq!(
+            invalid.halt_cause().unwrap().reason(),
+            HaltReason::InvalidConfig
+        );
+    }
+
+    #[test]
+    fn negative_limit_starts_halted() {
+        assert!(FixedSafetyKernel::new(-1).is_halted());
+        assert!(FixedSafetyKernel::from_f32_limit(-0.5).is_halted());
+        assert_eq!(
+            FixedSafetyKernel::try_new(-1).err(),
+            Some(ConfigError::NegativeLimit)
+        );
+    }
+}
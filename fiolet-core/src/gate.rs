@@ -0,0 +1,178 @@
+// fiolet-core/src/gate.rs
+
+//! Output gate: silence on HALT.
+//!
+//! `OutputGate` owns the bytes a host has produced for the current step
+//! but not yet shown to anyone. They are released only against the
+//! `ContinueToken` of a `Continue` decision for that step, so the type
+//! system proves every released byte had one. An `AtomicHalt` verdict
+//! wipes the buffer (volatile zeroing, so the wipe is not optimized
+//! away) and seals the gate: every later write and release is refused.
+//!
+//! This is the Rust counterpart of `output_emitted` in
+//! `formal_specs/FIOLET_Safety.tla`: bytes of a step whose decision was
+//! a halt are never emitted.
+//!
+//! Fixed capacity, allocation-free. Sealing is monotonic; there is no
+//! way to reopen a gate.
+
+use crate::{ContinueToken, Verdict};
+
+/// Why the gate refused a write or release.
+#[repr(u32)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum GateError {
+    /// The gate is sealed by a halt; nothing will ever be emitted again.
+    Sealed = 1,
+    /// The write does not fit in the remaining capacity (nothing written).
+    Overflow = 2,
+}
+
+/// Pending-output buffer of `N` bytes gated by kernel decisions.
+pub struct OutputGate<const N: usize> {
+    buffer: [u8; N],
+    len: usize,
+    sealed: bool,
+}
+
+impl<const N: usize> OutputGate<N> {
+    /// Empty, open gate.
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; N],
+            len: 0,
+            sealed: false,
+        }
+    }
+
+    /// Append bytes to the current step's pending output.
+    ///
+    /// All-or-nothing: on `Overflow` the pending output is unchanged.
+    pub fn write(&mut self, bytes: &[u8]) -> Result<(), GateError> {
+        if self.sealed {
+            return Err(GateError::Sealed);
+        }
+        let end = match self.len.checked_add(bytes.len()) {
+            Some(end) if end <= N => end,
+            _ => return Err(GateError::Overflow),
+        };
+        self.buffer[self.len..end].copy_from_slice(bytes);
+        self.len = end;
+        Ok(())
+    }
+
+    /// Close the current step with the kernel's verdict for it.
+    ///
+    /// `Continue` releases the pending bytes; `AtomicHalt` wipes and
+    /// seals the gate and returns `Sealed`.
+    pub fn close_step(&mut self, verdict: Verdict) -> Result<&[u8], GateError> {
+        match verdict.token() {
+            Some(token) => self.release(token),
+            None => {
+                self.seal();
+                Err(GateError::Sealed)
+            }
+        }
+    }
+
+    /// Release the pending bytes (valid until the next call on the gate)
+    /// and start a new, empty step. Consumes the step's token.
+    ///
+    /// Returns `Sealed` once the gate is sealed, whatever the token.
+    pub fn release(&mut self, _token: ContinueToken) -> Result<&[u8], GateError> {
+        if self.sealed {
+            return Err(GateError::Sealed);
+        }
+        let len = self.len;
+        self.len = 0;
+        Ok(&self.buffer[..len])
+    }
+
+    /// Wipe the buffer and refuse all further output.
+    ///
+    /// Also wipes bytes already released, so nothing of them outlives
+    /// the halt in this buffer.
+    pub fn seal(&mut self) {
+        self.sealed = true;
+        self.len = 0;
+        for byte in self.buffer.iter_mut() {
+            // SAFETY: `byte` is a valid, aligned, exclusive reference.
+            unsafe { core::ptr::write_volatile(byte, 0) };
+        }
+    }
+
+    /// Whether the gate is sealed.
+    pub const fn is_sealed(&self) -> bool {
+        self.sealed
+    }
+
+    /// Bytes pending for the current step.
+    pub const fn pending(&self) -> usize {
+        self.len
+    }
+
+    /// Capacity in bytes.
+    pub const fn capacity(&self) -> usize {
+        N
+    }
+}
+
+impl<const N: usize> Default for OutputGate<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SafetyDecision;
+
+    fn proceed() -> Verdict {
+        Verdict::from_decision(SafetyDecision::Continue)
+    }
+
+    fn halt() -> Verdict {
+        Verdict::from_decision(SafetyDecision::AtomicHalt)
+    }
+
+    #[test]
+    fn continue_releases_exactly_the_step_bytes() {
+        let mut gate = OutputGate::<16>::new();
+        gate.write(b"hello").unwrap();
+        gate.write(b", ").unwrap();
+        assert_eq!(gate.close_step(proceed()), Ok(&b"hello, "[..]));
+
+        assert_eq!(gate.pending(), 0);
+        gate.write(b"world").unwrap();
+        assert_eq!(gate.close_step(proceed()), Ok(&b"world"[..]));
+        assert_eq!(gate.close_step(proceed()), Ok(&b""[..]));
+    }
+
+    #[test]
+    fn halt_wipes_and_seals_forever() {
+        let mut gate = OutputGate::<8>::new();
+        gate.write(b"secret").unwrap();
+
+        assert_eq!(gate.close_step(halt()), Err(GateError::Sealed));
+        assert!(gate.is_sealed());
+        assert_eq!(gate.pending(), 0);
+        assert!(gate.buffer.iter().all(|&byte| byte == 0));
+
+        assert_eq!(gate.write(b"x"), Err(GateError::Sealed));
+        assert_eq!(gate.close_step(proceed()), Err(GateError::Sealed));
+    }
+
+    #[test]
+    fn overflow_is_all_or_nothing() {
+        let mut gate = OutputGate::<4>::new();
+        gate.write(b"abc").unwrap();
+        assert_eq!(gate.write(b"de"), Err(GateError::Overflow));
+        assert_eq!(gate.close_step(proceed()), Ok(&b"abc"[..]));
+        assert!(!gate.is_sealed());
+    }
+}
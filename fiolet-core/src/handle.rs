@@ -0,0 +1,228 @@
+// fiolet-core/src/handle.rs
+
+//! Opaque-handle pool behind the C ABI.
+//!
+//! Kernels live in fixed, statically allocated slots (no heap). The host
+//! receives an opaque handle encoding a pool tag, a per-slot generation
+//! and the slot index. Handles are decoded, NEVER dereferenced: a NULL,
+//! freed, stale or forged handle fails validation, and every ABI entry
+//! point maps that failure to `FIOLET_ATOMIC_HALT`.
+//!
+//! Handle layout (low 32 bits, valid on every pointer width >= 32):
+//!
+//! ```text
+//! bits 24..32  pool tag
+//! bits  8..24  slot generation (never 0)
+//! bits  0..8   slot index
+//! ```
+//!
+//! Each slot carries a spin lock, so concurrent calls on one handle are
+//! serialized instead of racing. Generations wrap after 65535 reuses of
+//! the same slot.
+
+use core::cell::UnsafeCell;
+use core::hint::spin_loop;
+use core::sync::atomic::{AtomicBool, AtomicU32, Ordering};
+
+const FREE: u32 = 0;
+const CLAIMED: u32 = 1;
+
+const INDEX_BITS: u32 = 8;
+const GENERATION_BITS: u32 = 16;
+const GENERATION_MASK: u32 = (1 << GENERATION_BITS) - 1;
+
+/// Largest pool size addressable by the handle layout.
+pub const MAX_POOL_SLOTS: usize = 1 << INDEX_BITS;
+
+struct Slot<T> {
+    /// `FREE`, `CLAIMED` (being initialized) or the live handle value.
+    state: AtomicU32,
+    generation: AtomicU32,
+    lock: AtomicBool,
+    value: UnsafeCell<Option<T>>,
+}
+
+impl<T> Slot<T> {
+    const fn new() -> Self {
+        Self {
+            state: AtomicU32::new(FREE),
+            generation: AtomicU32::new(0),
+            lock: AtomicBool::new(false),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    fn lock(&self) {
+        while self
+            .lock
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            spin_loop();
+        }
+    }
+
+    fn unlock(&self) {
+        self.lock.store(false, Ordering::Release);
+    }
+}
+
+/// Fixed pool of `N` slots handing out tagged opaque handles.
+pub(crate) struct HandlePool<T, const N: usize> {
+    tag: u8,
+    slots: [Slot<T>; N],
+}
+
+// SAFETY: every access to a slot value happens under that slot's lock.
+unsafe impl<T: Send, const N: usize> Sync for HandlePool<T, N> {}
+
+impl<T, const N: usize> HandlePool<T, N> {
+    /// Create an empty pool. `tag` distinguishes handles of different pools.
+    pub(crate) const fn new(tag: u8) -> Self {
+        assert!(N <= MAX_POOL_SLOTS);
+        assert!(tag != 0);
+
+        Self {
+            tag,
+            slots: [const { Slot::new() }; N],
+        }
+    }
+
+    /// Move `value` into a free slot. `None` when the pool is exhausted.
+    pub(crate) fn insert(&self, value: T) -> Option<usize> {
+        for (index, slot) in self.slots.iter().enumerate() {
+            if slot
+                .state
+                .compare_exchange(FREE, CLAIMED, Ordering::Acquire, Ordering::Relaxed)
+                .is_err()
+            {
+                continue;
+            }
+
+            let mut generation = slot
+                .generation
+                .fetch_add(1, Ordering::Relaxed)
+                .wrapping_add(1)
+                & GENERATION_MASK;
+            if generation == 0 {
+                generation = slot
+                    .generation
+                    .fetch_add(1, Ordering::Relaxed)
+                    .wrapping_add(1)
+                    & GENERATION_MASK;
+            }
+
+            let handle = (u32::from(self.tag) << (INDEX_BITS + GENERATION_BITS))
+                | (generation << INDEX_BITS)
+                | index as u32;
+
+            slot.lock();
+            // SAFETY: slot lock held.
+            unsafe { *slot.value.get() = Some(value) };
+            slot.state.store(handle, Ordering::Release);
+            slot.unlock();
+
+            return Some(handle as usize);
+        }
+
+        None
+    }
+
+    /// Slot addressed by `handle`, if its tag and index are well-formed.
+    fn slot(&self, handle: usize) -> Option<&Slot<T>> {
+        if handle > u32::MAX as usize
+            || handle >> (INDEX_BITS + GENERATION_BITS) != self.tag as usize
+        {
+            return None;
+        }
+        self.slots.get(handle & (MAX_POOL_SLOTS - 1))
+    }
+
+    /// Run `f` on the value behind a live handle.
+    ///
+    /// `None` for NULL, freed, stale or forged handles.
+    pub(crate) fn with<R>(&self, handle: usize, f: impl FnOnce(&mut T) -> R) -> Option<R> {
+        let slot = self.slot(handle)?;
+
+        slot.lock();
+        let result = if slot.state.load(Ordering::Acquire) == handle as u32 {
+            // SAFETY: slot lock held; the handle is live.
+            unsafe { (*slot.value.get()).as_mut().map(f) }
+        } else {
+            None
+        };
+        slot.unlock();
+
+        result
+    }
+
+    /// Release the slot behind a live handle. Invalid handles are ignored.
+    pub(crate) fn remove(&self, handle: usize) {
+        let Some(slot) = self.slot(handle) else {
+            return;
+        };
+
+        slot.lock();
+        if slot.state.load(Ordering::Acquire) == handle as u32 {
+            // SAFETY: slot lock held; the handle is live.
+            unsafe { *slot.value.get() = None };
+            slot.state.store(FREE, Ordering::Release);
+        }
+        slot.unlock();
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn live_handle_reaches_its_value() {
+        let pool: HandlePool<u32, 4> = HandlePool::new(0xA1);
+
+        let handle = pool.insert(7).unwrap();
+        assert_eq!(pool.with(handle, |v| *v), Some(7));
+    }
+
+    #[test]
+    fn null_forged_and_freed_handles_are_rejected() {
+        let pool: HandlePool<u32, 4> = HandlePool::new(0xA1);
+        let other: HandlePool<u32, 4> = HandlePool::new(0xA2);
+
+        let handle = pool.insert(7).unwrap();
+
+        assert_eq!(pool.with(0, |v| *v), None);
+        assert_eq!(pool.with(usize::MAX, |v| *v), None);
+        assert_eq!(pool.with(handle ^ (1 << INDEX_BITS), |v| *v), None);
+        assert_eq!(other.with(handle, |v| *v), None);
+
+        pool.remove(handle);
+        assert_eq!(pool.with(handle, |v| *v), None);
+    }
+
+    #[test]
+    fn reused_slot_gets_a_new_generation() {
+        let pool: HandlePool<u32, 1> = HandlePool::new(0xA1);
+
+        let first = pool.insert(1).unwrap();
+        pool.remove(first);
+        let second = pool.insert(2).unwrap();
+
+        assert_ne!(first, second);
+        assert_eq!(pool.with(first, |v| *v), None);
+        assert_eq!(pool.with(second, |v| *v), Some(2));
+    }
+
+    #[test]
+    fn exhausted_pool_refuses_insert() {
+        let pool: HandlePool<u32, 2> = HandlePool::new(0xA1);
+
+        assert!(pool.insert(1).is_some());
+        assert!(pool.insert(2).is_some());
+        assert!(pool.insert(3).is_none());
+    }
+}
@@ -0,0 +1,238 @@
+// fiolet-core/src/hierarchy.rs
+
+//! Hierarchical safety kernels with halt propagation.
+//!
+//! A `HierarchicalKernel` wraps one `SafetyKernel` and may reference a
+//! parent node, e.g. one session kernel with a child per capability
+//! (text, tool calls, retrieval). Halts travel in two directions:
+//!
+//! - down (always): a halted ancestor forces `AtomicHalt` on every
+//!   descendant, which latches `HaltReason::ParentHalted` itself;
+//! - up (opt-in, `escalating`): a halting child latches its parent with
+//!   its own cause, tagged with the child's channel.
+//!
+//! Both directions only ever latch; nothing clears a latch, so there is
+//! no path back from halted anywhere in the hierarchy.
+//!
+//! Evaluation takes `&self` so children can borrow their parent while it
+//! keeps evaluating. Nodes are single-threaded (`!Sync`); use one
+//! hierarchy per session. Fixed-size, allocation-free.
+
+use core::cell::RefCell;
+
+use crate::{HaltCause, HaltReason, SafetyConfig, SafetyDecision, SafetyKernel};
+
+/// Safety kernel node in a halt-propagating hierarchy.
+pub struct HierarchicalKernel<'p> {
+    /// Borrowed only inside this module, never across a call into
+    /// another node, so borrows cannot conflict.
+    kernel: RefCell<SafetyKernel>,
+    parent: Option<&'p HierarchicalKernel<'p>>,
+    /// Channel reported to the parent on escalation; `None` disables it.
+    escalation: Option<u32>,
+}
+
+impl<'p> HierarchicalKernel<'p> {
+    /// Create a root node (no parent).
+    ///
+    /// Fail-closed: an invalid configuration yields a node that starts
+    /// halted with `HaltReason::InvalidConfig`.
+    pub const fn root(config: SafetyConfig) -> Self {
+        Self {
+            kernel: RefCell::new(SafetyKernel::new(config)),
+            parent: None,
+            escalation: None,
+        }
+    }
+
+    /// Create a child of `parent`. It halts whenever any ancestor halts.
+    ///
+    /// Fail-closed like `root`; an invalid child configuration does not
+    /// affect the parent unless the child escalates.
+    pub const fn child(parent: &'p HierarchicalKernel<'p>, config: SafetyConfig) -> Self {
+        Self {
+            kernel: RefCell::new(SafetyKernel::new(config)),
+            parent: Some(parent),
+            escalation: None,
+        }
+    }
+
+    /// Escalate this node's halt to its parent, reported on `channel`.
+    ///
+    /// The parent latches the child's cause with `HaltCause::channel`
+    /// set to `channel`, then escalates further if it escalates itself.
+    /// No effect on a root node.
+    pub const fn escalating(mut self, channel: u32) -> Self {
+        self.escalation = Some(channel);
+        self
+    }
+
+    /// Evaluate a deviation.
+    ///
+    /// Returns `AtomicHalt` without inspecting `deviation` if any
+    /// ancestor is halted.
+    pub fn evaluate(&self, deviation: f32) -> SafetyDecision {
+        let ancestor_halted = self.ancestor_halted();
+        let decision = {
+            let mut kernel = self.kernel.borrow_mut();
+            if ancestor_halted {
+                let step = kernel.steps();
+                kernel.latch_external(HaltCause::new(HaltReason::ParentHalted, deviation, step))
+            } else {
+                kernel.evaluate(deviation).decision()
+            }
+        };
+
+        if decision == SafetyDecision::AtomicHalt {
+            self.escalate();
+        }
+        decision
+    }
+
+    /// Forward this node's cause to its parent if escalation is enabled.
+    ///
+    /// A `ParentHalted` cause is not forwarded: the parent is already halted.
+    fn escalate(&self) {
+        let (Some(parent), Some(channel)) = (self.parent, self.escalation) else {
+            return;
+        };
+        let cause = self.kernel.borrow().halt_cause();
+        match cause {
+            Some(cause) if cause.reason() != HaltReason::ParentHalted => {
+                parent.latch_from_child(cause.on_channel(channel));
+            }
+            _ => {}
+        }
+    }
+
+    /// Latch this node on behalf of an escalating child.
+    fn latch_from_child(&self, cause: HaltCause) {
+        self.kernel.borrow_mut().latch_external(cause);
+        self.escalate();
+    }
+
+    /// True if any ancestor reads as halted.
+    fn ancestor_halted(&self) -> bool {
+        let mut node = self.parent;
+        while let Some(parent) = node {
+            if parent.kernel.borrow().is_halted() {
+                return true;
+            }
+            node = parent.parent;
+        }
+        false
+    }
+
+    /// Query whether this node or any ancestor is halted.
+    pub fn is_halted(&self) -> bool {
+        self.kernel.borrow().is_halted() || self.ancestor_halted()
+    }
+
+    /// Cause recorded at latch time, or `None` while running.
+    ///
+    /// A node not yet evaluated since an ancestor halted reports
+    /// `ParentHalted`.
+    pub fn halt_cause(&self) -> Option<HaltCause> {
+        let kernel = self.kernel.borrow();
+        match kernel.halt_cause() {
+            Some(cause) => Some(cause),
+            None if self.ancestor_halted() => Some(HaltCause::new(
+                HaltReason::ParentHalted,
+                f32::NAN,
+                kernel.steps(),
+            )),
+            None => None,
+        }
+    }
+
+    /// Number of evaluations performed while running.
+    pub fn steps(&self) -> u64 {
+        self.kernel.borrow().steps()
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn halted_parent_halts_every_child() {
+        let session = HierarchicalKernel::root(SafetyConfig::new(1.0));
+        let text = HierarchicalKernel::child(&session, SafetyConfig::new(0.5));
+        let tools = HierarchicalKernel::child(&session, SafetyConfig::new(0.5));
+        let nested = HierarchicalKernel::child(&tools, SafetyConfig::new(0.5));
+
+        assert_eq!(text.evaluate(0.1), SafetyDecision::Continue);
+        assert_eq!(nested.evaluate(0.1), SafetyDecision::Continue);
+        assert_eq!(session.evaluate(2.0), SafetyDecision::AtomicHalt);
+
+        for node in [&text, &tools, &nested] {
+            assert!(node.is_halted());
+            assert_eq!(node.evaluate(0.0), SafetyDecision::AtomicHalt);
+            assert_eq!(
+                node.halt_cause().unwrap().reason(),
+                HaltReason::ParentHalted
+            );
+        }
+        assert_eq!(
+            session.halt_cause().unwrap().reason(),
+            HaltReason::ThresholdExceeded
+        );
+    }
+
+    #[test]
+    fn child_halt_escalates_only_when_enabled() {
+        let session = HierarchicalKernel::root(SafetyConfig::new(1.0));
+        let quiet = HierarchicalKernel::child(&session, SafetyConfig::new(0.5));
+        let loud = HierarchicalKernel::child(&session, SafetyConfig::new(0.5)).escalating(2);
+        let sibling = HierarchicalKernel::child(&session, SafetyConfig::new(0.5));
+
+        assert_eq!(quiet.evaluate(0.75), SafetyDecision::AtomicHalt);
+        assert!(!session.is_halted());
+        assert_eq!(sibling.evaluate(0.1), SafetyDecision::Continue);
+
+        assert_eq!(loud.evaluate(f32::NAN), SafetyDecision::AtomicHalt);
+        let cause = session.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::NonFinite);
+        assert_eq!(cause.channel(), Some(2));
+        assert_eq!(sibling.evaluate(0.1), SafetyDecision::AtomicHalt);
+    }
+
+    #[test]
+    fn escalation_chains_through_escalating_ancestors() {
+        let session = HierarchicalKernel::root(SafetyConfig::new(1.0));
+        let tools = HierarchicalKernel::child(&session, SafetyConfig::new(1.0)).escalating(1);
+        let shell = HierarchicalKernel::child(&tools, SafetyConfig::new(0.5)).escalating(7);
+
+        assert_eq!(shell.evaluate(0.9), SafetyDecision::AtomicHalt);
+        assert_eq!(tools.halt_cause().unwrap().channel(), Some(7));
+        // Re-tagged with each escalating node's own channel.
+        assert_eq!(session.halt_cause().unwrap().channel(), Some(1));
+        assert!(session.is_halted());
+    }
+
+    #[test]
+    fn no_path_back_from_halted() {
+        let session = HierarchicalKernel::root(SafetyConfig::new(1.0));
+        let child = HierarchicalKernel::child(&session, SafetyConfig::new(1.0)).escalating(0);
+
+        assert_eq!(session.evaluate(f32::INFINITY), SafetyDecision::AtomicHalt);
+        for deviation in [0.0, -1.0, 0.5] {
+            assert_eq!(child.evaluate(deviation), SafetyDecision::AtomicHalt);
+            assert_eq!(session.evaluate(deviation), SafetyDecision::AtomicHalt);
+        }
+        // A later child escalation never replaces the first cause.
+        assert_eq!(
+            session.halt_cause().unwrap().reason(),
+            HaltReason::NonFinite
+        );
+        assert_eq!(session.halt_cause().unwrap().channel(), None);
+
+        let orphan = HierarchicalKernel::child(&session, SafetyConfig::new(1.0));
+        assert!(orphan.is_halted());
+    }
+}
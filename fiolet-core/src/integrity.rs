@@ -0,0 +1,130 @@
+// fiolet-core/src/integrity.rs
+
+//! Redundant encodings for bit-flip resistant kernel state.
+//!
+//! A plain `bool` latch can be un-halted by a single memory fault. The
+//! latch here is a pair of complementary words holding one of two
+//! sparse codes; only the exact RUNNING pair reads as running, so any
+//! corruption resolves to halted. Un-halting a HALTED latch takes
+//! 40 specific bit flips.
+//!
+//! Configuration and mutable state are covered by FNV-1a (64-bit)
+//! checksums. FNV-1a maps every single-byte change to a different
+//! hash, so any single-bit fault is detected.
+
+/// Latch code for the running state.
+pub(crate) const RUNNING: u32 = 0x3CA5_5AC3;
+
+/// Latch code for the halted state (20 bits away from `RUNNING`,
+/// and not its complement, so swapped words never read as running).
+pub(crate) const HALTED: u32 = 0x6996_C33C;
+
+/// Halt latch stored as complementary words.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub(crate) struct LatchWord {
+    word: u32,
+    complement: u32,
+}
+
+impl LatchWord {
+    pub(crate) const RUNNING: Self = Self::encode(RUNNING);
+    pub(crate) const HALTED: Self = Self::encode(HALTED);
+
+    const fn encode(code: u32) -> Self {
+        Self {
+            word: code,
+            complement: !code,
+        }
+    }
+
+    /// Exactly the RUNNING encoding.
+    pub(crate) const fn is_running(&self) -> bool {
+        self.word == RUNNING && self.complement == !RUNNING
+    }
+
+    /// Exactly the HALTED encoding (a legitimate latch, not corruption).
+    pub(crate) const fn is_latched(&self) -> bool {
+        self.word == HALTED && self.complement == !HALTED
+    }
+
+    /// Fault injection: bits 0..32 hit `word`, 32..64 hit `complement`.
+    #[cfg(test)]
+    pub(crate) fn flip(&mut self, bit: u32) {
+        if bit < 32 {
+            self.word ^= 1 << bit;
+        } else {
+            self.complement ^= 1 << (bit - 32);
+        }
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// Start a checksum, domain-separated by `tag`.
+pub(crate) const fn fnv1a_start(tag: &[u8]) -> u64 {
+    fnv1a(FNV_OFFSET, tag)
+}
+
+/// FNV-1a (64-bit) over `bytes`, continuing from `hash`.
+pub(crate) const fn fnv1a(mut hash: u64, bytes: &[u8]) -> u64 {
+    let mut i = 0;
+    while i < bytes.len() {
+        hash ^= bytes[i] as u64;
+        hash = hash.wrapping_mul(FNV_PRIME);
+        i += 1;
+    }
+    hash
+}
+
+/// FNV-1a over the little-endian bytes of `value`.
+pub(crate) const fn fnv1a_u64(hash: u64, value: u64) -> u64 {
+    fnv1a(hash, &value.to_le_bytes())
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fnv1a_matches_reference_vectors() {
+        assert_eq!(fnv1a_start(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a_start(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a_start(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn no_single_or_paired_flip_reads_as_running() {
+        for bit in 0..64 {
+            let mut latch = LatchWord::HALTED;
+            latch.flip(bit);
+            assert!(!latch.is_running());
+
+            let mut latch = LatchWord::RUNNING;
+            if bit < 32 {
+                latch.word ^= 1 << bit;
+                latch.complement ^= 1 << bit;
+            }
+            assert_eq!(latch.is_running(), bit >= 32);
+            assert!(!latch.is_latched());
+        }
+
+        let swapped = LatchWord {
+            word: LatchWord::HALTED.complement,
+            complement: LatchWord::HALTED.word,
+        };
+        assert!(!swapped.is_running());
+        for word in [0, !0] {
+            let stuck = LatchWord {
+                word,
+                complement: word,
+            };
+            assert!(!stuck.is_running());
+        }
+    }
+}
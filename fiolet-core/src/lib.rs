@@ -24,7 +24,8 @@
 //! The only allowed state transition is: Running → Halted.
 //!
 //! I3 — No Hidden State
-//! All safety-relevant state is explicitly contained within `SafetyKernel`.
+//! All safety-relevant state is explicitly contained within `SafetyKernel`
+//! (step counter, window ring and previous sample included).
 //!
 //! I4 — Halt Dominance
 //! If the kernel is halted, every evaluation MUST return `AtomicHalt`.
@@ -45,6 +46,7 @@
 //! - panic = abort (kernel mode)
 //! - deterministic execution
 //! - fail-closed by construction
+//! - self-checking state (memory faults resolve to halt)
 //!
 //! This is a SAFETY INTERLOCK, not an application.
 //! Liveness is explicitly NOT guaranteed.
@@ -74,6 +76,26 @@ fn panic(_info: &PanicInfo) -> ! {
 // CORE TYPES (ABI STABLE)
 // ============================================================
 
+pub mod window;
+
+pub mod callback;
+
+use callback::{HaltHook, PendingCallback};
+use core::ffi::c_void;
+
+pub use callback::FioletHaltCallback;
+
+#[cfg(feature = "std")]
+pub use callback::NotifyingKernel;
+
+mod integrity;
+
+use integrity::{fnv1a, fnv1a_start, fnv1a_u64, LatchWord};
+
+pub use window::{WindowConfig, WINDOW_CAPACITY};
+
+use window::WindowState;
+
 /// Core decision returned by the safety kernel.
 ///
 /// repr(C) is REQUIRED for FFI correctness.
@@ -86,104 +108,1846 @@ pub enum SafetyDecision {
     AtomicHalt = 1,
 }
 
+/// Proof that the kernel returned `Continue` for one evaluation.
+///
+/// Zero-sized, neither `Copy` nor `Clone`, and constructible only inside
+/// this crate: a function taking a `ContinueToken` by value can only be
+/// called once per `Continue` decision. Emitting APIs (`OutputGate`)
+/// require one.
+///
+/// ```compile_fail
+/// let forged = fiolet_core::ContinueToken { _private: () };
+/// ```
+#[derive(PartialEq, Eq, Debug)]
+pub struct ContinueToken {
+    _private: (),
+}
+
+/// Result of `SafetyKernel::evaluate`: a `ContinueToken` or a halt.
+///
+/// Compares equal to the corresponding `SafetyDecision`.
+#[derive(PartialEq, Eq, Debug)]
+pub enum Verdict {
+    /// Generation may continue; the token authorizes one step of output.
+    Continue(ContinueToken),
+    /// Immediate and irreversible stop.
+    AtomicHalt,
+}
+
+impl Verdict {
+    /// Only kernels mint tokens.
+    pub(crate) const fn from_decision(decision: SafetyDecision) -> Self {
+        match decision {
+            SafetyDecision::Continue => Verdict::Continue(ContinueToken { _private: () }),
+            SafetyDecision::AtomicHalt => Verdict::AtomicHalt,
+        }
+    }
+
+    /// The plain decision, e.g. for logging or the C ABI.
+    pub const fn decision(&self) -> SafetyDecision {
+        match self {
+            Verdict::Continue(_) => SafetyDecision::Continue,
+            Verdict::AtomicHalt => SafetyDecision::AtomicHalt,
+        }
+    }
+
+    /// The token, if the verdict was `Continue`.
+    pub fn token(self) -> Option<ContinueToken> {
+        match self {
+            Verdict::Continue(token) => Some(token),
+            Verdict::AtomicHalt => None,
+        }
+    }
+}
+
+impl PartialEq<SafetyDecision> for Verdict {
+    fn eq(&self, decision: &SafetyDecision) -> bool {
+        self.decision() == *decision
+    }
+}
+
+/// Reason a kernel configuration was rejected.
+///
+/// Codes are stable; 0 is reserved for "valid" on the C side.
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ConfigError {
+    /// A limit is NaN or infinite.
+    NonFiniteLimit = 1,
+    /// A limit is negative.
+    NegativeLimit = 2,
+    /// Window length, K or soft limit is out of range.
+    InvalidWindow = 3,
+    /// Slope limit is NaN or negative.
+    InvalidSlopeLimit = 4,
+    /// More channels than the kernel can hold.
+    InvalidChannelCount = 5,
+    /// A change-detector parameter is out of range.
+    InvalidDetectorParameter = 6,
+    /// The configuration uses a rule this kernel variant cannot enforce.
+    UnsupportedRule = 7,
+    /// Baseline is NaN or infinite.
+    NonFiniteBaseline = 8,
+    /// Lower limit is NaN or negative.
+    InvalidLowerLimit = 9,
+}
+
+/// Fail-closed validation shared by every kernel configuration type.
+///
+/// Kernels built from a configuration that fails validation start halted
+/// (`HaltReason::InvalidConfig`); `try_new` constructors surface the error.
+pub trait Validate {
+    /// Check every field; the first violation is reported.
+    fn validate(&self) -> Result<(), ConfigError>;
+}
+
+/// Shared rule for absolute limits: finite and non-negative.
+pub(crate) const fn check_limit(limit: f32) -> Result<(), ConfigError> {
+    if !limit.is_finite() {
+        Err(ConfigError::NonFiniteLimit)
+    } else if limit < 0.0 {
+        Err(ConfigError::NegativeLimit)
+    } else {
+        Ok(())
+    }
+}
+
 /// Immutable safety threshold configuration.
 ///
 /// Plain data only.
 #[repr(C)]
-#[derive(Copy, Clone)]
+#[derive(Copy, Clone, PartialEq, Debug)]
 pub struct SafetyConfig {
-    /// Absolute deviation threshold.
+    /// Upper deviation threshold: maximum `input − baseline`.
     pub deviation_limit: f32,
+    /// Maximum number of evaluations (temporal horizon).
+    /// `NO_STEP_BUDGET` (0) disables the budget.
+    pub max_steps: u64,
+    /// Optional K-of-N soft-limit window (disabled by default).
+    pub window: WindowConfig,
+    /// Maximum |d_t − d_{t−1}| between consecutive deviations.
+    /// `NO_SLOPE_LIMIT` (+∞) disables the slope rule.
+    pub slope_limit: f32,
+    /// Reference subtracted from every input before any rule applies.
+    /// `0` evaluates inputs as precomputed deviations.
+    pub baseline: f32,
+    /// Lower deviation threshold: maximum `baseline − input`.
+    /// `NO_LOWER_LIMIT` (+∞) leaves the band open below.
+    pub lower_limit: f32,
 }
 
 impl SafetyConfig {
+    /// `max_steps` value meaning "no step budget".
+    pub const NO_STEP_BUDGET: u64 = 0;
+
+    /// `slope_limit` value meaning "no slope rule".
+    pub const NO_SLOPE_LIMIT: f32 = f32::INFINITY;
+
+    /// `lower_limit` value meaning "no lower bound".
+    pub const NO_LOWER_LIMIT: f32 = f32::INFINITY;
+
     /// Create a new safety configuration.
+    ///
+    /// Inputs are deviations: latch when one exceeds `deviation_limit`.
+    /// Not validated; see `try_new` and `validate`.
+    pub const fn new(deviation_limit: f32) -> Self {
+        Self {
+            deviation_limit,
+            max_steps: Self::NO_STEP_BUDGET,
+            window: WindowConfig::DISABLED,
+            slope_limit: Self::NO_SLOPE_LIMIT,
+            baseline: 0.0,
+            lower_limit: Self::NO_LOWER_LIMIT,
+        }
+    }
+
+    /// Create a baseline-relative configuration.
+    ///
+    /// Inputs are raw metrics: latch when |metric − baseline| > `threshold`.
+    /// Not validated; see `validate`.
+    pub const fn relative(baseline: f32, threshold: f32) -> Self {
+        let mut config = Self::new(threshold);
+        config.baseline = baseline;
+        config.lower_limit = threshold;
+        config
+    }
+
+    /// Create a new safety configuration, rejecting invalid limits.
+    pub const fn try_new(deviation_limit: f32) -> Result<Self, ConfigError> {
+        let config = Self::new(deviation_limit);
+        match config.validate() {
+            Ok(()) => Ok(config),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Check every field.
+    ///
+    /// - `deviation_limit` finite and non-negative
+    /// - `window` valid whenever enabled
+    /// - `slope_limit` non-negative, `NO_SLOPE_LIMIT` allowed
+    /// - `baseline` finite
+    /// - `lower_limit` non-negative, `NO_LOWER_LIMIT` allowed
+    pub const fn validate(&self) -> Result<(), ConfigError> {
+        if let Err(error) = check_limit(self.deviation_limit) {
+            return Err(error);
+        }
+        if let Err(error) = self.window.validate() {
+            return Err(error);
+        }
+        if self.slope_limit.is_nan() || self.slope_limit < 0.0 {
+            return Err(ConfigError::InvalidSlopeLimit);
+        }
+        if !self.baseline.is_finite() {
+            return Err(ConfigError::NonFiniteBaseline);
+        }
+        if self.lower_limit.is_nan() || self.lower_limit < 0.0 {
+            return Err(ConfigError::InvalidLowerLimit);
+        }
+        Ok(())
+    }
+
+    /// Bound the number of evaluations.
+    ///
+    /// Evaluation number `max_steps + 1` latches `AtomicHalt`.
+    pub const fn with_max_steps(mut self, max_steps: u64) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Latch when `k` of the last `n` deviations exceed `soft_limit`.
+    ///
+    /// Applies in addition to the hard `deviation_limit` rule. The
+    /// comparison is signed: with a baseline, only excursions above it
+    /// (`input - baseline > soft_limit`) count, and drops below the
+    /// baseline are bounded only by `lower_limit`.
+    pub const fn with_window(mut self, soft_limit: f32, k: u8, n: u8) -> Self {
+        self.window = WindowConfig::new(soft_limit, k, n);
+        self
+    }
+
+    /// Bound the rate of change between consecutive deviations.
+    ///
+    /// Latches when |d_t − d_{t−1}| > `slope_limit` or the difference
+    /// is non-finite. The first evaluation has no predecessor.
+    pub const fn with_slope_limit(mut self, slope_limit: f32) -> Self {
+        self.slope_limit = slope_limit;
+        self
+    }
+
+    /// Whether the slope rule participates in evaluation.
+    pub const fn has_slope_limit(&self) -> bool {
+        self.slope_limit != Self::NO_SLOPE_LIMIT
+    }
+
+    /// Accept inputs in `[baseline − lower_limit, baseline + upper_limit]`.
+    ///
+    /// Replaces `deviation_limit` with `upper_limit`.
+    pub const fn with_band(mut self, lower_limit: f32, upper_limit: f32) -> Self {
+        self.lower_limit = lower_limit;
+        self.deviation_limit = upper_limit;
+        self
+    }
+
+    /// Whether inputs below the baseline are bounded.
+    pub const fn has_lower_limit(&self) -> bool {
+        self.lower_limit != Self::NO_LOWER_LIMIT
+    }
+
+    /// Whether inputs are plain deviations checked against
+    /// `deviation_limit` alone (no baseline, no lower limit).
+    pub const fn is_absolute(&self) -> bool {
+        self.baseline == 0.0 && !self.has_lower_limit()
+    }
+
+    /// Signed deviation `input − baseline` if it lies inside the band,
+    /// otherwise the rule it violates.
+    ///
+    /// Fail-closed: a non-finite input is `NonFinite`; a finite input
+    /// whose deviation overflows lies outside every band.
+    pub(crate) const fn deviation(&self, input: f32) -> Result<f32, HaltReason> {
+        if !input.is_finite() {
+            return Err(HaltReason::NonFinite);
+        }
+        let deviation = input - self.baseline;
+        if !deviation.is_finite()
+            || deviation > self.deviation_limit
+            || deviation < -self.lower_limit
+        {
+            return Err(HaltReason::ThresholdExceeded);
+        }
+        Ok(deviation)
+    }
+}
+
+impl Validate for SafetyConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        SafetyConfig::validate(self)
+    }
+}
+
+/// C layout of `FioletSafetyConfig`, frozen at ABI 1.3.
+///
+/// Has no baseline band: it always converts to an absolute-mode
+/// `SafetyConfig`, so hosts built against minor 3 keep both their layout
+/// and their meaning.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AbiSafetyConfig {
+    pub deviation_limit: f32,
+    pub max_steps: u64,
+    pub window: WindowConfig,
+    pub slope_limit: f32,
+}
+
+impl AbiSafetyConfig {
+    /// Same defaults as `SafetyConfig::new`.
     pub const fn new(deviation_limit: f32) -> Self {
-        Self { deviation_limit }
+        let config = SafetyConfig::new(deviation_limit);
+        Self {
+            deviation_limit,
+            max_steps: config.max_steps,
+            window: config.window,
+            slope_limit: config.slope_limit,
+        }
+    }
+
+    /// The kernel configuration (absolute mode).
+    pub const fn config(&self) -> SafetyConfig {
+        SafetyConfig {
+            max_steps: self.max_steps,
+            window: self.window,
+            slope_limit: self.slope_limit,
+            ..SafetyConfig::new(self.deviation_limit)
+        }
+    }
+}
+
+/// C layout of `FioletSafetyConfigV2` (ABI 1.4): the 1.3 fields plus the
+/// baseline band.
+///
+/// All-zero band fields mean what ABI 1.3 meant: no baseline, no lower
+/// bound.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct AbiSafetyConfigV2 {
+    pub base: AbiSafetyConfig,
+    pub baseline: f32,
+    pub lower_limit: f32,
+    /// `1` applies `lower_limit`, `0` leaves the band open below. Any
+    /// other value is rejected (`ConfigError::InvalidLowerLimit`).
+    pub lower_bounded: u8,
+}
+
+impl AbiSafetyConfigV2 {
+    /// The kernel configuration.
+    pub const fn config(&self) -> SafetyConfig {
+        let mut config = self.base.config();
+        config.baseline = self.baseline;
+        config.lower_limit = match self.lower_bounded {
+            0 => SafetyConfig::NO_LOWER_LIMIT,
+            1 => self.lower_limit,
+            // Fails validation: the kernel starts halted.
+            _ => f32::NAN,
+        };
+        config
+    }
+}
+
+/// Reason recorded when the kernel latches `AtomicHalt`.
+#[repr(C)]
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum HaltReason {
+    /// Deviation exceeded the configured limit.
+    ThresholdExceeded = 1,
+    /// Deviation was NaN or infinite.
+    NonFinite = 2,
+    /// Input was malformed (NULL pointer, length mismatch).
+    InvalidInput = 3,
+    /// Evaluation count exceeded `SafetyConfig::max_steps`.
+    StepBudgetExceeded = 4,
+    /// K of the last N deviations exceeded the window soft limit.
+    WindowExceeded = 5,
+    /// |d_t − d_{t−1}| exceeded the slope limit or was non-finite.
+    SlopeExceeded = 6,
+    /// Configuration failed validation; the kernel started halted.
+    InvalidConfig = 7,
+    /// Redundant state encoding was inconsistent (memory fault).
+    StateCorrupted = 8,
+    /// Redundant replicas disagreed; `HaltCause::channel` names the
+    /// dissenting replica.
+    ReplicaDisagreement = 9,
+    /// An ancestor in a kernel hierarchy halted.
+    ParentHalted = 10,
+}
+
+impl HaltReason {
+    /// Reason for a numeric code (as stored or sent over the C ABI).
+    pub const fn from_code(code: u32) -> Option<Self> {
+        Some(match code {
+            1 => Self::ThresholdExceeded,
+            2 => Self::NonFinite,
+            3 => Self::InvalidInput,
+            4 => Self::StepBudgetExceeded,
+            5 => Self::WindowExceeded,
+            6 => Self::SlopeExceeded,
+            7 => Self::InvalidConfig,
+            8 => Self::StateCorrupted,
+            9 => Self::ReplicaDisagreement,
+            10 => Self::ParentHalted,
+            _ => return None,
+        })
+    }
+}
+
+/// Immutable record of why, when and on what the kernel halted.
+///
+/// Captured exactly once, at latch time. Plain data only.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct HaltCause {
+    reason: HaltReason,
+    value: f32,
+    step: u64,
+    channel: u32,
+}
+
+impl HaltCause {
+    /// Channel sentinel for causes not attributed to a channel.
+    pub const NO_CHANNEL: u32 = u32::MAX;
+
+    pub(crate) const fn new(reason: HaltReason, value: f32, step: u64) -> Self {
+        Self {
+            reason,
+            value,
+            step,
+            channel: Self::NO_CHANNEL,
+        }
+    }
+
+    pub(crate) const fn on_channel(mut self, channel: u32) -> Self {
+        self.channel = channel;
+        self
+    }
+
+    /// Rebuild a cause recorded earlier (durable journal, trace).
+    ///
+    /// Kernels record their own causes; this exists only to restore one.
+    pub const fn restore(reason: HaltReason, value: f32, step: u64, channel: Option<u32>) -> Self {
+        let cause = Self::new(reason, value, step);
+        match channel {
+            Some(channel) => cause.on_channel(channel),
+            None => cause,
+        }
+    }
+
+    /// Why the kernel halted.
+    pub const fn reason(&self) -> HaltReason {
+        self.reason
+    }
+
+    /// Offending input value (NaN for inputs without a value).
+    pub const fn value(&self) -> f32 {
+        self.value
+    }
+
+    /// Zero-based index of the evaluation that latched the halt.
+    pub const fn step(&self) -> u64 {
+        self.step
+    }
+
+    /// Channel that latched the halt, if any.
+    pub const fn channel(&self) -> Option<u32> {
+        if self.channel == Self::NO_CHANNEL {
+            None
+        } else {
+            Some(self.channel)
+        }
     }
 }
 
+/// Result of evaluating a slice of deviations.
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub struct BatchOutcome {
+    /// Decision after the last element (`AtomicHalt` if any element halted).
+    pub decision: SafetyDecision,
+    /// Index of the first element that returned `AtomicHalt`, if any.
+    pub first_halt: Option<usize>,
+}
+
 /// Safety kernel state.
 ///
 /// Minimal, deterministic, monotonic.
+///
+/// Self-checking: the halt latch is stored as complementary words, and
+/// the configuration and the mutable state are each covered by a
+/// checksum. Every `evaluate` and `is_halted` verifies the encoding; any
+/// inconsistency reads as halted and latches `HaltReason::StateCorrupted`.
+///
+/// Deliberately neither `Copy` nor `Clone`: a copy could be fed an
+/// unsafe value and discarded, sidestepping the latch. `TypedKernel`
+/// additionally moves the halt state into the type.
 #[repr(C)]
-#[derive(Copy, Clone)]
 pub struct SafetyKernel {
     config: SafetyConfig,
-    halted: bool,
+    config_check: u64,
+    latch: LatchWord,
+    steps: u64,
+    cause: HaltCause,
+    window: WindowState,
+    previous: f32,
+    has_previous: bool,
+    hook: HaltHook,
+    state_check: u64,
+}
+
+/// Checksum over every configuration field.
+const fn config_checksum(config: &SafetyConfig) -> u64 {
+    let mut hash = fnv1a_start(b"fiolet-kernel-config");
+    hash = fnv1a_u64(hash, config.deviation_limit.to_bits() as u64);
+    hash = fnv1a_u64(hash, config.max_steps);
+    hash = fnv1a_u64(hash, config.window.soft_limit.to_bits() as u64);
+    hash = fnv1a(hash, &[config.window.k, config.window.n]);
+    hash = fnv1a_u64(hash, config.slope_limit.to_bits() as u64);
+    hash = fnv1a_u64(hash, config.baseline.to_bits() as u64);
+    fnv1a_u64(hash, config.lower_limit.to_bits() as u64)
 }
 
 impl SafetyKernel {
     /// Create a new safety kernel.
+    ///
+    /// Fail-closed: an invalid configuration yields a kernel that starts
+    /// halted with `HaltReason::InvalidConfig`.
     pub const fn new(config: SafetyConfig) -> Self {
-        Self {
+        let valid = config.validate().is_ok();
+
+        let kernel = Self {
             config,
-            halted: false,
+            config_check: config_checksum(&config),
+            latch: if valid {
+                LatchWord::RUNNING
+            } else {
+                LatchWord::HALTED
+            },
+            steps: 0,
+            // Only observable once halted.
+            cause: HaltCause::new(HaltReason::InvalidConfig, f32::NAN, 0),
+            window: WindowState::new(),
+            previous: 0.0,
+            has_previous: false,
+            hook: HaltHook::NONE,
+            state_check: 0,
+        };
+        kernel.sealed()
+    }
+
+    /// Create a new safety kernel, rejecting an invalid configuration.
+    pub const fn try_new(config: SafetyConfig) -> Result<Self, ConfigError> {
+        match config.validate() {
+            Ok(()) => Ok(Self::new(config)),
+            Err(error) => Err(error),
         }
     }
 
-    /// Evaluate a single deviation signal.
-    pub fn evaluate(&mut self, deviation: f32) -> SafetyDecision {
-        if self.halted {
-            return SafetyDecision::AtomicHalt;
+    /// Create a kernel that is already halted with `cause`, e.g. restored
+    /// from a durable latch after a restart (I1 across processes).
+    pub const fn new_halted(config: SafetyConfig, cause: HaltCause, steps: u64) -> Self {
+        let mut kernel = Self::new(config);
+        kernel.latch = LatchWord::HALTED;
+        kernel.cause = cause;
+        kernel.steps = steps;
+        kernel.sealed()
+    }
+
+    /// Evaluate a single deviation signal, or a raw metric when the
+    /// configuration sets a baseline.
+    ///
+    /// Returns a `ContinueToken` only on `Continue`. Invokes the halt
+    /// callback, if registered, when this call latches.
+    pub fn evaluate(&mut self, deviation: f32) -> Verdict {
+        let decision = self.evaluate_deferred(deviation);
+        if decision == SafetyDecision::AtomicHalt {
+            self.notify_halt();
         }
+        Verdict::from_decision(decision)
+    }
 
-        if !deviation.is_finite() {
-            self.halted = true;
+    /// `evaluate` without invoking the halt callback; the caller takes it
+    /// with `take_pending_callback`.
+    pub(crate) fn evaluate_deferred(&mut self, input: f32) -> SafetyDecision {
+        if !self.check_integrity() {
             return SafetyDecision::AtomicHalt;
         }
 
-        if deviation > self.config.deviation_limit {
-            self.halted = true;
-            SafetyDecision::AtomicHalt
-        } else {
-            SafetyDecision::Continue
+        let step = self.steps;
+        self.steps = self.steps.saturating_add(1);
+
+        let budget = self.config.max_steps;
+        if budget != SafetyConfig::NO_STEP_BUDGET && step >= budget {
+            return self.latch(HaltCause::new(HaltReason::StepBudgetExceeded, input, step));
+        }
+
+        let deviation = match self.config.deviation(input) {
+            Ok(deviation) => deviation,
+            Err(reason) => return self.latch(HaltCause::new(reason, input, step)),
+        };
+
+        if self.config.has_slope_limit() && self.has_previous {
+            let slope = deviation - self.previous;
+            if !slope.is_finite() || slope.abs() > self.config.slope_limit {
+                return self.latch(HaltCause::new(HaltReason::SlopeExceeded, input, step));
+            }
+        }
+        self.previous = deviation;
+        self.has_previous = true;
+
+        if self.window.record(&self.config.window, deviation) {
+            return self.latch(HaltCause::new(HaltReason::WindowExceeded, input, step));
+        }
+
+        self.state_check = self.state_checksum();
+        SafetyDecision::Continue
+    }
+
+    /// Evaluate deviations in order, exactly like repeated `evaluate` calls.
+    ///
+    /// Stops at the first element that returns `AtomicHalt`; every later
+    /// element is treated as halted without being inspected.
+    pub fn evaluate_slice(&mut self, deviations: &[f32]) -> BatchOutcome {
+        let outcome = self.evaluate_slice_deferred(deviations);
+        if outcome.decision == SafetyDecision::AtomicHalt {
+            self.notify_halt();
+        }
+        outcome
+    }
+
+    /// `evaluate_slice` without invoking the halt callback.
+    pub(crate) fn evaluate_slice_deferred(&mut self, deviations: &[f32]) -> BatchOutcome {
+        for (index, &deviation) in deviations.iter().enumerate() {
+            if self.evaluate_deferred(deviation) == SafetyDecision::AtomicHalt {
+                return BatchOutcome {
+                    decision: SafetyDecision::AtomicHalt,
+                    first_halt: Some(index),
+                };
+            }
+        }
+
+        BatchOutcome {
+            decision: if self.is_halted() {
+                SafetyDecision::AtomicHalt
+            } else {
+                SafetyDecision::Continue
+            },
+            first_halt: None,
+        }
+    }
+
+    /// Latch halt, recording its cause. The first cause is never replaced.
+    fn latch(&mut self, cause: HaltCause) -> SafetyDecision {
+        if self.latch.is_running() {
+            self.latch = LatchWord::HALTED;
+            self.cause = cause;
+        }
+        SafetyDecision::AtomicHalt
+    }
+
+    /// Copy of `self` with the state checksum recomputed.
+    const fn sealed(mut self) -> Self {
+        self.state_check = self.state_checksum();
+        self
+    }
+
+    /// Checksum over the mutable running state.
+    const fn state_checksum(&self) -> u64 {
+        let mut hash = fnv1a_start(b"fiolet-kernel-state");
+        hash = fnv1a_u64(hash, self.config_check);
+        hash = fnv1a_u64(hash, self.steps);
+        hash = self.window.seal(hash);
+        hash = fnv1a_u64(hash, self.previous.to_bits() as u64);
+        fnv1a(hash, &[self.has_previous as u8])
+    }
+
+    /// Running with an intact encoding: exact RUNNING latch and matching
+    /// checksums. Everything else must be treated as halted.
+    const fn is_running_intact(&self) -> bool {
+        self.latch.is_running()
+            && self.config_check == config_checksum(&self.config)
+            && self.state_check == self.state_checksum()
+    }
+
+    /// True iff evaluation may proceed.
+    ///
+    /// An inconsistent encoding latches `StateCorrupted`, replacing any
+    /// recorded cause: it can no longer be trusted.
+    fn check_integrity(&mut self) -> bool {
+        if self.is_running_intact() {
+            return true;
+        }
+        if !self.latch.is_latched() {
+            self.latch = LatchWord::HALTED;
+            self.cause = HaltCause::new(HaltReason::StateCorrupted, f32::NAN, self.steps);
+        }
+        false
+    }
+
+    /// Latch halt on malformed input (NULL pointer from the C ABI).
+    pub(crate) fn latch_invalid_input(&mut self) -> SafetyDecision {
+        self.check_integrity();
+        self.latch(HaltCause::new(
+            HaltReason::InvalidInput,
+            f32::NAN,
+            self.steps,
+        ))
+    }
+
+    /// Latch halt because a wrapping component's configuration is invalid.
+    pub(crate) fn latch_invalid_config(&mut self) {
+        self.check_integrity();
+        self.latch(HaltCause::new(
+            HaltReason::InvalidConfig,
+            f32::NAN,
+            self.steps,
+        ));
+    }
+
+    /// Latch halt on behalf of an enclosing component (e.g. a parent).
+    pub(crate) fn latch_external(&mut self, cause: HaltCause) -> SafetyDecision {
+        self.check_integrity();
+        self.latch(cause)
+    }
+
+    /// Register a callback invoked exactly once, synchronously, when the
+    /// kernel latches; see `callback`.
+    ///
+    /// If the kernel is already halted the callback runs before this
+    /// returns. Returns false (registering nothing) if a callback is
+    /// already armed.
+    pub fn set_halt_callback(
+        &mut self,
+        callback: FioletHaltCallback,
+        context: *mut c_void,
+    ) -> bool {
+        if !self.arm_halt_callback(callback, context) {
+            return false;
+        }
+        self.notify_halt();
+        true
+    }
+
+    /// Arm `callback` without invoking it; false if one is already armed.
+    pub(crate) fn arm_halt_callback(
+        &mut self,
+        callback: FioletHaltCallback,
+        context: *mut c_void,
+    ) -> bool {
+        if self.hook.is_armed() {
+            return false;
+        }
+        self.hook = HaltHook::new(callback, context);
+        true
+    }
+
+    /// Disarm and return the callback once the kernel is halted.
+    pub(crate) fn take_pending_callback(&mut self) -> Option<PendingCallback> {
+        if !self.hook.is_armed() {
+            return None;
+        }
+        let cause = self.halt_cause()?;
+        self.hook
+            .take()
+            .map(|hook| PendingCallback::new(hook, cause))
+    }
+
+    fn notify_halt(&mut self) {
+        if let Some(pending) = self.take_pending_callback() {
+            pending.fire();
         }
     }
 
     /// Query whether the kernel is already halted.
+    ///
+    /// A corrupted encoding reads as halted.
     pub const fn is_halted(&self) -> bool {
-        self.halted
+        !self.is_running_intact()
+    }
+
+    /// Cause recorded at latch time, or `None` while running.
+    ///
+    /// A corrupted encoding not yet latched by `evaluate` reports
+    /// `StateCorrupted`.
+    pub const fn halt_cause(&self) -> Option<HaltCause> {
+        if self.latch.is_latched() {
+            Some(self.cause)
+        } else if self.is_running_intact() {
+            None
+        } else {
+            Some(HaltCause::new(
+                HaltReason::StateCorrupted,
+                f32::NAN,
+                self.steps,
+            ))
+        }
+    }
+
+    /// Number of evaluations performed while running.
+    ///
+    /// Includes the latching evaluation; frozen once halted.
+    pub const fn steps(&self) -> u64 {
+        self.steps
     }
 }
 
 // ============================================================
-// C ABI (FFI SAFE INTERFACE)
+// TYPESTATE API (RUNNING / HALTED)
 // ============================================================
 
-#[no_mangle]
-pub extern "C" fn fiolet_kernel_new(limit: f32) -> SafetyKernel {
-    SafetyKernel::new(SafetyConfig::new(limit))
-}
+pub mod typestate;
 
-#[no_mangle]
-pub extern "C" fn fiolet_kernel_evaluate(
-    kernel: &mut SafetyKernel,
-    deviation: f32,
-) -> SafetyDecision {
-    kernel.evaluate(deviation)
-}
+pub use typestate::{Halted, KernelState, Running, Transition, TypedKernel};
 
-#[no_mangle]
-pub extern "C" fn fiolet_kernel_is_halted(kernel: &SafetyKernel) -> bool {
-    kernel.is_halted()
-}
+// ============================================================
+// MULTI-CHANNEL KERNEL
+// ============================================================
+
+pub mod multi;
+
+pub use multi::{AbiMultiChannelKernel, MultiChannelKernel, FIOLET_MAX_CHANNELS};
 
 // ============================================================
-// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// SEQUENTIAL CHANGE DETECTORS
 // ============================================================
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+pub mod detectors;
 
-    #[test]
-    fn atomic_halt_is_irreversible() {
-        let mut kernel = SafetyKernel::new(SafetyConfig::new(0.5));
+pub use detectors::{ChangeDetector, Cusum, DetectorKernel, Ewma, PageHinkley};
 
-        assert_eq!(kernel.evaluate(0.1), SafetyDecision::Continue);
-        assert_eq!(kernel.evaluate(1.0), SafetyDecision::AtomicHalt);
-        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+// ============================================================
+// FIXED-POINT KERNEL (Q16.16)
+// ============================================================
+
+pub mod fixed;
+
+pub use fixed::{FixedSafetyKernel, Q16};
+
+// ============================================================
+// TRIPLE-MODULAR-REDUNDANT KERNEL
+// ============================================================
+
+pub mod tmr;
+
+pub use tmr::{TmrSafetyKernel, TMR_REPLICAS};
+
+// ============================================================
+// LOCK-FREE KERNEL (CONCURRENT EVALUATORS)
+// ============================================================
+
+#[cfg(target_has_atomic = "64")]
+pub mod atomic;
+
+#[cfg(target_has_atomic = "64")]
+pub use atomic::AtomicSafetyKernel;
+
+// ============================================================
+// HIERARCHICAL KERNELS (HALT PROPAGATION)
+// ============================================================
+
+pub mod hierarchy;
+
+pub use hierarchy::HierarchicalKernel;
+
+// ============================================================
+// OUTPUT GATE (SILENCE ON HALT)
+// ============================================================
+
+pub mod gate;
+
+pub use gate::{GateError, OutputGate};
+
+// ============================================================
+// ABI VERSION & BUILD PROVENANCE
+// ============================================================
+
+pub mod version;
+
+pub use version::{BuildInfo, BUILD_INFO, FIOLET_ABI_VERSION, FIOLET_CAPABILITIES};
+
+// ============================================================
+// C ABI (FFI SAFE INTERFACE)
+// ============================================================
+//
+// Kernels behind the C ABI live in fixed static pools (see `handle`).
+// The host only ever holds opaque handles; a NULL, freed, stale or
+// forged handle is never dereferenced and always reads as halted.
+
+pub mod handle;
+
+use handle::HandlePool;
+
+/// Opaque `FioletKernel*` handle. Never dereferenced.
+#[repr(C)]
+pub struct FioletKernel {
+    _opaque: [u8; 0],
+}
+
+/// Opaque `FioletMultiKernel*` handle. Never dereferenced.
+#[repr(C)]
+pub struct FioletMultiKernel {
+    _opaque: [u8; 0],
+}
+
+/// Opaque `FioletFixedKernel*` handle. Never dereferenced.
+#[repr(C)]
+pub struct FioletFixedKernel {
+    _opaque: [u8; 0],
+}
+
+/// Opaque `FioletTmrKernel*` handle. Never dereferenced.
+#[repr(C)]
+pub struct FioletTmrKernel {
+    _opaque: [u8; 0],
+}
+
+/// Concurrently live `FioletKernel` handles.
+pub const FIOLET_MAX_KERNELS: usize = 64;
+
+/// Concurrently live `FioletMultiKernel` handles.
+pub const FIOLET_MAX_MULTI_KERNELS: usize = 16;
+
+/// Concurrently live `FioletFixedKernel` handles.
+pub const FIOLET_MAX_FIXED_KERNELS: usize = 16;
+
+/// Concurrently live `FioletTmrKernel` handles.
+pub const FIOLET_MAX_TMR_KERNELS: usize = 16;
+
+static KERNELS: HandlePool<SafetyKernel, FIOLET_MAX_KERNELS> = HandlePool::new(0xF1);
+static MULTI_KERNELS: HandlePool<AbiMultiChannelKernel, FIOLET_MAX_MULTI_KERNELS> =
+    HandlePool::new(0xF2);
+static FIXED_KERNELS: HandlePool<FixedSafetyKernel, FIOLET_MAX_FIXED_KERNELS> =
+    HandlePool::new(0xF3);
+static TMR_KERNELS: HandlePool<TmrSafetyKernel, FIOLET_MAX_TMR_KERNELS> = HandlePool::new(0xF4);
+
+/// Cause reported for a handle that does not name a live kernel.
+const INVALID_HANDLE_CAUSE: HaltCause = HaltCause::new(HaltReason::InvalidInput, f32::NAN, 0);
+
+/// Handle value returned to C; NULL when the pool is exhausted.
+fn into_handle<T>(handle: Option<usize>) -> *mut T {
+    handle.map_or(core::ptr::null_mut(), core::ptr::without_provenance_mut)
+}
+
+/// Copy `cause` into `out`; false while running or when `out` is NULL.
+fn write_cause(cause: Option<HaltCause>, out: Option<&mut HaltCause>) -> bool {
+    match (cause, out) {
+        (Some(cause), Some(out)) => {
+            *out = cause;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// ABI version of the linked kernel, `(major << 16) | minor`.
+///
+/// A host built against header version `H` is compatible iff the major
+/// parts are equal and the runtime minor is at least `H`'s minor.
+#[no_mangle]
+pub extern "C" fn fiolet_abi_version() -> u32 {
+    FIOLET_ABI_VERSION
+}
+
+/// `FIOLET_CAP_*` bits supported by the linked kernel.
+#[no_mangle]
+pub extern "C" fn fiolet_capabilities() -> u64 {
+    FIOLET_CAPABILITIES
+}
+
+/// Crate version as a static NUL-terminated string.
+#[no_mangle]
+pub extern "C" fn fiolet_crate_version() -> *const core::ffi::c_char {
+    version::FIOLET_CRATE_VERSION.as_ptr().cast()
+}
+
+/// Build fingerprint (crate version, ABI version, features, config hash).
+#[no_mangle]
+pub extern "C" fn fiolet_build_fingerprint() -> u64 {
+    BUILD_INFO.fingerprint
+}
+
+/// Copy the full build provenance record into `out`.
+///
+/// Returns false (and writes nothing) when `out` is NULL.
+#[no_mangle]
+pub extern "C" fn fiolet_build_info(out: Option<&mut BuildInfo>) -> bool {
+    match out {
+        Some(out) => {
+            *out = BUILD_INFO;
+            true
+        }
+        None => false,
+    }
+}
+
+/// Create a new safety kernel.
+///
+/// Fail-closed: a NaN, infinite or negative limit yields a kernel that
+/// starts halted. Returns NULL when the kernel pool is exhausted.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_new(limit: f32) -> *mut FioletKernel {
+    fiolet_kernel_new_with_config(AbiSafetyConfig::new(limit))
+}
+
+/// Create a new safety kernel from a full configuration.
+///
+/// Fail-closed: an invalid configuration yields a kernel that starts halted.
+/// Returns NULL when the kernel pool is exhausted.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_new_with_config(config: AbiSafetyConfig) -> *mut FioletKernel {
+    into_handle(KERNELS.insert(SafetyKernel::new(config.config())))
+}
+
+/// Create a new safety kernel from a configuration with a baseline band.
+///
+/// Fail-closed: an invalid configuration yields a kernel that starts halted.
+/// Returns NULL when the kernel pool is exhausted.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_new_with_config_v2(config: AbiSafetyConfigV2) -> *mut FioletKernel {
+    into_handle(KERNELS.insert(SafetyKernel::new(config.config())))
+}
+
+/// Validate a configuration without creating a kernel.
+///
+/// Returns 0 if valid, otherwise the `ConfigError` code.
+#[no_mangle]
+pub extern "C" fn fiolet_config_validate(config: AbiSafetyConfig) -> u32 {
+    config_code(config.config())
+}
+
+/// Validate a configuration with a baseline band without creating a kernel.
+///
+/// Returns 0 if valid, otherwise the `ConfigError` code.
+#[no_mangle]
+pub extern "C" fn fiolet_config_validate_v2(config: AbiSafetyConfigV2) -> u32 {
+    config_code(config.config())
+}
+
+fn config_code(config: SafetyConfig) -> u32 {
+    match config.validate() {
+        Ok(()) => 0,
+        Err(error) => error as u32,
+    }
+}
+
+/// Evaluate a deviation signal.
+///
+/// Fail-closed: an invalid handle yields `AtomicHalt`.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_evaluate(
+    kernel: *mut FioletKernel,
+    deviation: f32,
+) -> SafetyDecision {
+    let (decision, pending) = KERNELS
+        .with(kernel.addr(), |kernel| {
+            let decision = kernel.evaluate_deferred(deviation);
+            (decision, kernel.take_pending_callback())
+        })
+        .unwrap_or((SafetyDecision::AtomicHalt, None));
+
+    // Outside the slot lock: the callback may re-enter this handle.
+    if let Some(pending) = pending {
+        pending.fire();
+    }
+    decision
+}
+
+/// Sentinel written to `first_halt` when no element halted.
+pub const FIOLET_NO_HALT_INDEX: usize = usize::MAX;
+
+/// Evaluate `len` deviations in order; see `SafetyKernel::evaluate_slice`.
+///
+/// If `first_halt` is non-NULL it receives the index of the first halting
+/// element, or `FIOLET_NO_HALT_INDEX`.
+///
+/// Fail-closed: an invalid handle or a NULL `deviations` pointer halts at
+/// index 0.
+///
+/// # Safety
+///
+/// `deviations` must be NULL or point to `len` readable `f32` values.
+#[no_mangle]
+pub unsafe extern "C" fn fiolet_kernel_evaluate_slice(
+    kernel: *mut FioletKernel,
+    deviations: *const f32,
+    len: usize,
+    first_halt: Option<&mut usize>,
+) -> SafetyDecision {
+    let (outcome, pending) = KERNELS
+        .with(kernel.addr(), |kernel| {
+            let outcome = if deviations.is_null() {
+                BatchOutcome {
+                    decision: kernel.latch_invalid_input(),
+                    first_halt: Some(0),
+                }
+            } else {
+                kernel.evaluate_slice_deferred(core::slice::from_raw_parts(deviations, len))
+            };
+            (outcome, kernel.take_pending_callback())
+        })
+        .unwrap_or((
+            BatchOutcome {
+                decision: SafetyDecision::AtomicHalt,
+                first_halt: Some(0),
+            },
+            None,
+        ));
+
+    if let Some(pending) = pending {
+        pending.fire();
+    }
+
+    if let Some(first_halt) = first_halt {
+        *first_halt = outcome.first_halt.unwrap_or(FIOLET_NO_HALT_INDEX);
+    }
+    outcome.decision
+}
+
+/// Query halted state. An invalid handle reads as halted.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_is_halted(kernel: *const FioletKernel) -> bool {
+    KERNELS
+        .with(kernel.addr(), |kernel| kernel.is_halted())
+        .unwrap_or(true)
+}
+
+/// Number of evaluations performed. An invalid handle reports 0.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_steps(kernel: *const FioletKernel) -> u64 {
+    KERNELS
+        .with(kernel.addr(), |kernel| kernel.steps())
+        .unwrap_or(0)
+}
+
+/// Copy the recorded halt cause into `out`.
+///
+/// Returns false (and leaves `out` untouched) while the kernel is running
+/// or when `out` is NULL. An invalid handle reports `InvalidInput`.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_halt_cause(
+    kernel: *const FioletKernel,
+    out: Option<&mut HaltCause>,
+) -> bool {
+    let cause = KERNELS
+        .with(kernel.addr(), |kernel| kernel.halt_cause())
+        .unwrap_or(Some(INVALID_HANDLE_CAUSE));
+    write_cause(cause, out)
+}
+
+/// Register a halt callback, invoked exactly once when the kernel latches.
+///
+/// Runs synchronously on the thread whose call latched the kernel, after
+/// the handle is unlocked (it may call back into this handle). If the
+/// kernel is already halted it runs before this returns.
+///
+/// Returns false if `callback` is NULL or a callback is already armed.
+/// For an invalid handle the callback runs immediately with an
+/// `InvalidInput` cause (the handle reads as halted) and false is returned.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_set_halt_callback(
+    kernel: *mut FioletKernel,
+    callback: Option<FioletHaltCallback>,
+    context: *mut c_void,
+) -> bool {
+    let Some(callback) = callback else {
+        return false;
+    };
+
+    let (armed, pending) = KERNELS
+        .with(kernel.addr(), |kernel| {
+            let armed = kernel.arm_halt_callback(callback, context);
+            (armed, kernel.take_pending_callback())
+        })
+        .unwrap_or_else(|| {
+            let pending =
+                PendingCallback::new(HaltHook::new(callback, context), INVALID_HANDLE_CAUSE);
+            (false, Some(pending))
+        });
+
+    if let Some(pending) = pending {
+        pending.fire();
+    }
+    armed
+}
+
+/// Release a kernel slot. NULL and invalid handles are ignored.
+///
+/// The handle is dead afterwards: every later call through it halts.
+/// An armed halt callback is dropped without being invoked.
+#[no_mangle]
+pub extern "C" fn fiolet_kernel_free(kernel: *mut FioletKernel) {
+    KERNELS.remove(kernel.addr());
+}
+
+/// Create a multi-channel kernel with one limit per channel.
+///
+/// Fail-closed: a NULL `limits` pointer, `len == 0` or
+/// `len > FIOLET_MAX_CHANNELS` yields an already-halted kernel; zero
+/// channels start halted with `InvalidConfig`. Returns NULL when the
+/// pool is exhausted.
+///
+/// # Safety
+///
+/// `limits` must be NULL or point to `len` readable `f32` values.
+#[no_mangle]
+pub unsafe extern "C" fn fiolet_multi_kernel_new(
+    limits: *const f32,
+    len: usize,
+) -> *mut FioletMultiKernel {
+    let kernel = if limits.is_null() {
+        let mut kernel = AbiMultiChannelKernel::new([0.0; FIOLET_MAX_CHANNELS]);
+        kernel.latch_invalid_input();
+        kernel
+    } else {
+        AbiMultiChannelKernel::from_limits(core::slice::from_raw_parts(limits, len))
+    };
+
+    into_handle(MULTI_KERNELS.insert(kernel))
+}
+
+/// Evaluate one deviation per configured channel.
+///
+/// Fail-closed: an invalid handle, a NULL `deviations` pointer or a `len`
+/// different from the configured channel count yields `AtomicHalt`.
+///
+/// # Safety
+///
+/// `deviations` must be NULL or point to `len` readable `f32` values.
+#[no_mangle]
+pub unsafe extern "C" fn fiolet_multi_kernel_evaluate(
+    kernel: *mut FioletMultiKernel,
+    deviations: *const f32,
+    len: usize,
+) -> SafetyDecision {
+    MULTI_KERNELS
+        .with(kernel.addr(), |kernel| {
+            if deviations.is_null() {
+                kernel.latch_invalid_input()
+            } else {
+                kernel.evaluate_channels(core::slice::from_raw_parts(deviations, len))
+            }
+        })
+        .unwrap_or(SafetyDecision::AtomicHalt)
+}
+
+#[no_mangle]
+pub extern "C" fn fiolet_multi_kernel_is_halted(kernel: *const FioletMultiKernel) -> bool {
+    MULTI_KERNELS
+        .with(kernel.addr(), |kernel| kernel.is_halted())
+        .unwrap_or(true)
+}
+
+/// Copy the recorded halt cause into `out`; see `fiolet_kernel_halt_cause`.
+#[no_mangle]
+pub extern "C" fn fiolet_multi_kernel_halt_cause(
+    kernel: *const FioletMultiKernel,
+    out: Option<&mut HaltCause>,
+) -> bool {
+    let cause = MULTI_KERNELS
+        .with(kernel.addr(), |kernel| kernel.halt_cause())
+        .unwrap_or(Some(INVALID_HANDLE_CAUSE));
+    write_cause(cause, out)
+}
+
+/// Index of the channel that latched the halt, or -1 if none.
+#[no_mangle]
+pub extern "C" fn fiolet_multi_kernel_tripped_channel(kernel: *const FioletMultiKernel) -> i32 {
+    match MULTI_KERNELS.with(kernel.addr(), |kernel| kernel.tripped_channel()) {
+        Some(Some(channel)) => channel as i32,
+        _ => -1,
+    }
+}
+
+/// Release a multi-channel kernel slot; see `fiolet_kernel_free`.
+#[no_mangle]
+pub extern "C" fn fiolet_multi_kernel_free(kernel: *mut FioletMultiKernel) {
+    MULTI_KERNELS.remove(kernel.addr());
+}
+
+/// Create a Q16.16 kernel from a fixed-point limit.
+///
+/// Returns NULL when the pool is exhausted.
+#[no_mangle]
+pub extern "C" fn fiolet_fixed_kernel_new(limit: Q16) -> *mut FioletFixedKernel {
+    into_handle(FIXED_KERNELS.insert(FixedSafetyKernel::new(limit)))
+}
+
+/// Create a Q16.16 kernel from an `f32` limit (rounded toward −∞).
+///
+/// Fail-closed: a non-finite limit yields an already-halted kernel.
+/// Returns NULL when the pool is exhausted.
+#[no_mangle]
+pub extern "C" fn fiolet_fixed_kernel_new_f32(limit: f32) -> *mut FioletFixedKernel {
+    into_handle(FIXED_KERNELS.insert(FixedSafetyKernel::from_f32_limit(limit)))
+}
+
+#[no_mangle]
+pub extern "C" fn fiolet_fixed_kernel_evaluate(
+    kernel: *mut FioletFixedKernel,
+    deviation: Q16,
+) -> SafetyDecision {
+    FIXED_KERNELS
+        .with(kernel.addr(), |kernel| kernel.evaluate(deviation))
+        .unwrap_or(SafetyDecision::AtomicHalt)
+}
+
+/// Convert an `f32` deviation (rounded toward +∞, saturating) and evaluate.
+///
+/// Non-finite input latches halt.
+#[no_mangle]
+pub extern "C" fn fiolet_fixed_kernel_evaluate_f32(
+    kernel: *mut FioletFixedKernel,
+    deviation: f32,
+) -> SafetyDecision {
+    FIXED_KERNELS
+        .with(kernel.addr(), |kernel| kernel.evaluate_f32(deviation))
+        .unwrap_or(SafetyDecision::AtomicHalt)
+}
+
+#[no_mangle]
+pub extern "C" fn fiolet_fixed_kernel_is_halted(kernel: *const FioletFixedKernel) -> bool {
+    FIXED_KERNELS
+        .with(kernel.addr(), |kernel| kernel.is_halted())
+        .unwrap_or(true)
+}
+
+/// Copy the recorded halt cause into `out`; see `fiolet_kernel_halt_cause`.
+#[no_mangle]
+pub extern "C" fn fiolet_fixed_kernel_halt_cause(
+    kernel: *const FioletFixedKernel,
+    out: Option<&mut HaltCause>,
+) -> bool {
+    let cause = FIXED_KERNELS
+        .with(kernel.addr(), |kernel| kernel.halt_cause())
+        .unwrap_or(Some(INVALID_HANDLE_CAUSE));
+    write_cause(cause, out)
+}
+
+/// Release a fixed-point kernel slot; see `fiolet_kernel_free`.
+#[no_mangle]
+pub extern "C" fn fiolet_fixed_kernel_free(kernel: *mut FioletFixedKernel) {
+    FIXED_KERNELS.remove(kernel.addr());
+}
+
+/// Create a triple-modular-redundant kernel from a full configuration.
+///
+/// Fail-closed: an invalid configuration yields a kernel that starts halted.
+/// Returns NULL when the pool is exhausted.
+#[no_mangle]
+pub extern "C" fn fiolet_tmr_kernel_new(config: AbiSafetyConfig) -> *mut FioletTmrKernel {
+    into_handle(TMR_KERNELS.insert(TmrSafetyKernel::new(config.config())))
+}
+
+/// Create a triple-modular-redundant kernel with a baseline band.
+///
+/// Fail-closed: an invalid configuration yields a kernel that starts halted.
+/// Returns NULL when the pool is exhausted.
+#[no_mangle]
+pub extern "C" fn fiolet_tmr_kernel_new_v2(config: AbiSafetyConfigV2) -> *mut FioletTmrKernel {
+    into_handle(TMR_KERNELS.insert(TmrSafetyKernel::new(config.config())))
+}
+
+/// Evaluate a deviation on all three replicas.
+///
+/// `Continue` only if every replica returned `Continue`. Fail-closed: an
+/// invalid handle yields `AtomicHalt`.
+#[no_mangle]
+pub extern "C" fn fiolet_tmr_kernel_evaluate(
+    kernel: *mut FioletTmrKernel,
+    deviation: f32,
+) -> SafetyDecision {
+    TMR_KERNELS
+        .with(kernel.addr(), |kernel| kernel.evaluate(deviation))
+        .unwrap_or(SafetyDecision::AtomicHalt)
+}
+
+#[no_mangle]
+pub extern "C" fn fiolet_tmr_kernel_is_halted(kernel: *const FioletTmrKernel) -> bool {
+    TMR_KERNELS
+        .with(kernel.addr(), |kernel| kernel.is_halted())
+        .unwrap_or(true)
+}
+
+/// Copy the recorded halt cause into `out`; see `fiolet_kernel_halt_cause`.
+///
+/// For `ReplicaDisagreement`, `channel` is the dissenting replica.
+#[no_mangle]
+pub extern "C" fn fiolet_tmr_kernel_halt_cause(
+    kernel: *const FioletTmrKernel,
+    out: Option<&mut HaltCause>,
+) -> bool {
+    let cause = TMR_KERNELS
+        .with(kernel.addr(), |kernel| kernel.halt_cause())
+        .unwrap_or(Some(INVALID_HANDLE_CAUSE));
+    write_cause(cause, out)
+}
+
+/// Release a TMR kernel slot; see `fiolet_kernel_free`.
+#[no_mangle]
+pub extern "C" fn fiolet_tmr_kernel_free(kernel: *mut FioletTmrKernel) {
+    TMR_KERNELS.remove(kernel.addr());
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn atomic_halt_is_irreversible() {
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(0.5));
+
+        assert_eq!(kernel.evaluate(0.1), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(1.0), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+    }
+
+    #[test]
+    fn invalid_config_is_rejected() {
+        assert_eq!(
+            SafetyConfig::try_new(f32::NAN).err(),
+            Some(ConfigError::NonFiniteLimit)
+        );
+        assert_eq!(
+            SafetyConfig::try_new(f32::INFINITY).err(),
+            Some(ConfigError::NonFiniteLimit)
+        );
+        assert_eq!(
+            SafetyConfig::try_new(-1.0).err(),
+            Some(ConfigError::NegativeLimit)
+        );
+        assert_eq!(
+            SafetyConfig::new(1.0).with_window(0.5, 4, 3).validate(),
+            Err(ConfigError::InvalidWindow)
+        );
+        assert_eq!(
+            SafetyConfig::new(1.0).with_slope_limit(f32::NAN).validate(),
+            Err(ConfigError::InvalidSlopeLimit)
+        );
+        assert_eq!(
+            SafetyConfig::relative(f32::NAN, 1.0).validate(),
+            Err(ConfigError::NonFiniteBaseline)
+        );
+        assert_eq!(
+            SafetyConfig::new(1.0).with_band(-0.5, 1.0).validate(),
+            Err(ConfigError::InvalidLowerLimit)
+        );
+        assert!(SafetyConfig::try_new(0.0).is_ok());
+    }
+
+    #[test]
+    fn invalid_config_kernel_starts_halted() {
+        assert!(SafetyKernel::try_new(SafetyConfig::new(f32::NAN)).is_err());
+
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(f32::NAN));
+        assert!(kernel.is_halted());
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.steps(), 0);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::InvalidConfig
+        );
+
+        let handle = fiolet_kernel_new(-0.5);
+        assert!(fiolet_kernel_is_halted(handle));
+        fiolet_kernel_free(handle);
+        assert_eq!(
+            fiolet_config_validate(AbiSafetyConfig::new(-0.5)),
+            ConfigError::NegativeLimit as u32
+        );
+    }
+
+    #[test]
+    fn abi_configs_keep_old_meaning_when_band_fields_are_zero() {
+        let base = AbiSafetyConfig::new(1.0);
+        assert_eq!(base.config(), SafetyConfig::new(1.0));
+
+        let zeroed = AbiSafetyConfigV2 {
+            base,
+            baseline: 0.0,
+            lower_limit: 0.0,
+            lower_bounded: 0,
+        };
+        assert_eq!(zeroed.config(), SafetyConfig::new(1.0));
+
+        let band = AbiSafetyConfigV2 {
+            baseline: 10.0,
+            lower_limit: 0.5,
+            lower_bounded: 1,
+            ..zeroed
+        };
+        assert_eq!(
+            band.config(),
+            SafetyConfig::relative(10.0, 1.0).with_band(0.5, 1.0)
+        );
+        assert_eq!(
+            fiolet_config_validate_v2(AbiSafetyConfigV2 {
+                lower_bounded: 2,
+                ..band
+            }),
+            ConfigError::InvalidLowerLimit as u32
+        );
+    }
+
+    #[test]
+    fn slice_matches_sequential_evaluation() {
+        let config = SafetyConfig::new(1.0).with_slope_limit(0.6);
+        let inputs = [0.1, 0.5, 0.9, 1.6, 0.0, f32::NAN];
+
+        let mut batch = SafetyKernel::new(config);
+        let outcome = batch.evaluate_slice(&inputs);
+
+        let mut sequential = SafetyKernel::new(config);
+        let decisions: Vec<_> = inputs.iter().map(|&d| sequential.evaluate(d)).collect();
+        let first = decisions
+            .iter()
+            .position(|d| *d == SafetyDecision::AtomicHalt);
+
+        assert_eq!(outcome.first_halt, first);
+        assert_eq!(outcome.first_halt, Some(3));
+        assert_eq!(outcome.decision, SafetyDecision::AtomicHalt);
+        assert_eq!(batch.steps(), sequential.steps());
+        assert_eq!(batch.halt_cause(), sequential.halt_cause());
+
+        let again = batch.evaluate_slice(&[0.0]);
+        assert_eq!(again.first_halt, Some(0));
+    }
+
+    #[test]
+    fn slice_c_abi_reports_index() {
+        let kernel = fiolet_kernel_new(1.0);
+        let inputs = [0.2, 0.3];
+        let mut first_halt = 0;
+
+        let decision = unsafe {
+            fiolet_kernel_evaluate_slice(
+                kernel,
+                inputs.as_ptr(),
+                inputs.len(),
+                Some(&mut first_halt),
+            )
+        };
+        assert_eq!(decision, SafetyDecision::Continue);
+        assert_eq!(first_halt, FIOLET_NO_HALT_INDEX);
+
+        let decision = unsafe {
+            fiolet_kernel_evaluate_slice(kernel, core::ptr::null(), 4, Some(&mut first_halt))
+        };
+        assert_eq!(decision, SafetyDecision::AtomicHalt);
+        assert_eq!(first_halt, 0);
+
+        let mut cause = INVALID_HANDLE_CAUSE;
+        assert!(fiolet_kernel_halt_cause(kernel, Some(&mut cause)));
+        assert_eq!(cause.reason(), HaltReason::InvalidInput);
+        assert_eq!(cause.step(), 2);
+        fiolet_kernel_free(kernel);
+    }
+
+    #[test]
+    fn invalid_handles_always_halt() {
+        let forged = 0xF100_0105usize as *mut FioletKernel;
+
+        for handle in [core::ptr::null_mut(), forged] {
+            assert_eq!(
+                fiolet_kernel_evaluate(handle, 0.0),
+                SafetyDecision::AtomicHalt
+            );
+            assert!(fiolet_kernel_is_halted(handle));
+            assert_eq!(fiolet_kernel_steps(handle), 0);
+            fiolet_kernel_free(handle);
+        }
+
+        let freed = fiolet_kernel_new(1.0);
+        assert_eq!(fiolet_kernel_evaluate(freed, 0.0), SafetyDecision::Continue);
+        fiolet_kernel_free(freed);
+        assert_eq!(
+            fiolet_kernel_evaluate(freed, 0.0),
+            SafetyDecision::AtomicHalt
+        );
+        assert!(!fiolet_kernel_halt_cause(freed, None));
+
+        let mut cause = HaltCause::new(HaltReason::ThresholdExceeded, 0.0, 0);
+        assert!(fiolet_kernel_halt_cause(freed, Some(&mut cause)));
+        assert_eq!(cause.reason(), HaltReason::InvalidInput);
+
+        let multi = core::ptr::null_mut::<FioletMultiKernel>();
+        assert_eq!(
+            unsafe { fiolet_multi_kernel_evaluate(multi, [0.0].as_ptr(), 1) },
+            SafetyDecision::AtomicHalt
+        );
+        assert_eq!(fiolet_multi_kernel_tripped_channel(multi), -1);
+
+        let fixed = fiolet_fixed_kernel_new(fixed::Q16_ONE);
+        fiolet_fixed_kernel_free(fixed);
+        assert_eq!(
+            fiolet_fixed_kernel_evaluate(fixed, 0),
+            SafetyDecision::AtomicHalt
+        );
+        assert!(fiolet_fixed_kernel_is_halted(fixed));
+    }
+
+    #[test]
+    fn halt_cause_is_recorded_once() {
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(0.5));
+
+        assert_eq!(kernel.evaluate(0.1), SafetyDecision::Continue);
+        assert_eq!(kernel.halt_cause(), None);
+
+        assert_eq!(kernel.evaluate(0.75), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.evaluate(f32::NAN), SafetyDecision::AtomicHalt);
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::ThresholdExceeded);
+        assert_eq!(cause.value(), 0.75);
+        assert_eq!(cause.step(), 1);
+        assert_eq!(cause.channel(), None);
+    }
+
+    #[test]
+    fn non_finite_cause_is_distinguished() {
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(0.5));
+
+        kernel.evaluate(f32::NEG_INFINITY);
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::NonFinite);
+        assert_eq!(cause.value(), f32::NEG_INFINITY);
+        assert_eq!(cause.step(), 0);
+    }
+
+    #[test]
+    fn step_budget_latches_halt() {
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(1.0).with_max_steps(3));
+
+        for _ in 0..3 {
+            assert_eq!(kernel.evaluate(0.0), SafetyDecision::Continue);
+        }
+        assert_eq!(kernel.steps(), 3);
+
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.steps(), 4);
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::StepBudgetExceeded);
+        assert_eq!(cause.step(), 3);
+
+        kernel.evaluate(0.0);
+        assert_eq!(kernel.steps(), 4);
+    }
+
+    #[test]
+    fn sustained_soft_exceedance_latches_halt() {
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(1.0).with_window(0.8, 3, 4));
+
+        assert_eq!(kernel.evaluate(0.9), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(0.9), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(0.1), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(0.9), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::WindowExceeded);
+        assert_eq!(cause.step(), 3);
+    }
+
+    #[test]
+    fn sharp_jump_latches_halt() {
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(10.0).with_slope_limit(0.5));
+
+        assert_eq!(kernel.evaluate(-5.0), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(-4.6), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(-5.0), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(-3.0), SafetyDecision::AtomicHalt);
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::SlopeExceeded);
+        assert_eq!(cause.value(), -3.0);
+        assert_eq!(cause.step(), 3);
+    }
+
+    #[test]
+    fn continue_token_only_accompanies_continue() {
+        assert_eq!(core::mem::size_of::<ContinueToken>(), 0);
+
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(1.0));
+        assert!(kernel.evaluate(0.5).token().is_some());
+        assert!(kernel.evaluate(1.5).token().is_none());
+        assert!(kernel.evaluate(0.0).token().is_none());
+    }
+
+    #[test]
+    fn restored_kernel_is_halted_with_its_cause() {
+        for code in 1..=10 {
+            assert_eq!(HaltReason::from_code(code).unwrap() as u32, code);
+        }
+        assert_eq!(HaltReason::from_code(0), None);
+        assert_eq!(HaltReason::from_code(11), None);
+
+        let cause = HaltCause::restore(HaltReason::SlopeExceeded, 2.5, 7, Some(1));
+        let mut kernel = SafetyKernel::new_halted(SafetyConfig::new(1.0), cause, 8);
+        assert!(kernel.is_halted());
+        assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.halt_cause(), Some(cause));
+        assert_eq!(kernel.steps(), 8);
+    }
+
+    #[test]
+    fn flipped_latch_never_unhalts() {
+        let halted = || {
+            let mut kernel = SafetyKernel::new(SafetyConfig::new(0.5));
+            kernel.evaluate(1.0);
+            kernel
+        };
+
+        for bit in 0..64 {
+            let mut kernel = halted();
+            kernel.latch.flip(bit);
+            assert!(kernel.is_halted());
+            assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+            assert_eq!(
+                kernel.halt_cause().unwrap().reason(),
+                HaltReason::StateCorrupted
+            );
+        }
+    }
+
+    #[test]
+    fn corrupted_config_or_state_latches_halt() {
+        let running = || {
+            let mut kernel = SafetyKernel::new(SafetyConfig::new(0.5).with_window(0.4, 2, 4));
+            kernel.evaluate(0.45);
+            kernel
+        };
+
+        let faults: [fn(&mut SafetyKernel, u32); 5] = [
+            |k, bit| {
+                k.config.deviation_limit =
+                    f32::from_bits(k.config.deviation_limit.to_bits() ^ (1 << bit))
+            },
+            |k, bit| k.config.max_steps ^= 1 << bit,
+            |k, bit| k.steps ^= 1 << bit,
+            |k, bit| k.previous = f32::from_bits(k.previous.to_bits() ^ (1 << bit)),
+            |k, bit| k.latch.flip(bit + 32),
+        ];
+
+        for fault in faults {
+            for bit in 0..32 {
+                let mut kernel = running();
+                fault(&mut kernel, bit);
+
+                assert!(kernel.is_halted());
+                assert_eq!(kernel.evaluate(0.0), SafetyDecision::AtomicHalt);
+                assert_eq!(
+                    kernel.halt_cause().unwrap().reason(),
+                    HaltReason::StateCorrupted
+                );
+            }
+        }
+
+        assert_eq!(running().evaluate(0.0), SafetyDecision::Continue);
+    }
+
+    #[test]
+    fn non_finite_slope_latches_halt() {
+        let limit = f32::MAX;
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(limit).with_slope_limit(limit));
+
+        assert_eq!(kernel.evaluate(-f32::MAX), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(f32::MAX), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::SlopeExceeded
+        );
+    }
+
+    #[test]
+    fn absolute_mode_leaves_negative_deviations_unbounded() {
+        let config = SafetyConfig::new(1.0);
+        assert!(config.is_absolute());
+
+        let mut kernel = SafetyKernel::new(config);
+        assert_eq!(kernel.evaluate(-f32::MAX), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(1.0), SafetyDecision::Continue);
+    }
+
+    #[test]
+    fn relative_mode_bounds_distance_from_baseline() {
+        let config = SafetyConfig::relative(10.0, 0.5);
+        assert!(!config.is_absolute());
+
+        for (metric, expected) in [
+            (10.5, SafetyDecision::Continue),
+            (9.5, SafetyDecision::Continue),
+            (10.75, SafetyDecision::AtomicHalt),
+            (9.25, SafetyDecision::AtomicHalt),
+        ] {
+            let mut kernel = SafetyKernel::new(config);
+            assert_eq!(kernel.evaluate(metric).decision(), expected, "{metric}");
+        }
+    }
+
+    #[test]
+    fn asymmetric_band_applies_each_limit_on_its_side() {
+        let config = SafetyConfig::relative(0.0, 1.0).with_band(0.25, 2.0);
+        let mut kernel = SafetyKernel::new(config);
+
+        assert_eq!(kernel.evaluate(2.0), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(-0.25), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(-0.5), SafetyDecision::AtomicHalt);
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::ThresholdExceeded);
+        assert_eq!(cause.value(), -0.5);
+    }
+
+    #[test]
+    fn relative_mode_fails_closed_on_non_finite_metrics() {
+        for metric in [f32::NAN, f32::INFINITY, f32::NEG_INFINITY] {
+            let mut kernel = SafetyKernel::new(SafetyConfig::relative(1.0, 0.5));
+            assert_eq!(kernel.evaluate(metric), SafetyDecision::AtomicHalt);
+            assert_eq!(kernel.halt_cause().unwrap().reason(), HaltReason::NonFinite);
+        }
+
+        // Finite metric, overflowing deviation: outside every band.
+        let config = SafetyConfig::relative(f32::MAX, f32::MAX).with_band(0.0, f32::MAX);
+        let mut kernel = SafetyKernel::new(config);
+        assert_eq!(kernel.evaluate(-f32::MAX), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::ThresholdExceeded
+        );
+    }
+
+    #[test]
+    fn window_and_slope_see_deviation_from_baseline() {
+        let config = SafetyConfig::relative(100.0, 10.0).with_slope_limit(1.0);
+        let mut kernel = SafetyKernel::new(config);
+
+        assert_eq!(kernel.evaluate(100.0), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(100.5), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(102.0), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::SlopeExceeded
+        );
+
+        let config = SafetyConfig::relative(100.0, 10.0).with_window(1.0, 2, 2);
+        let mut kernel = SafetyKernel::new(config);
+        assert_eq!(kernel.evaluate(101.5), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(101.5), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::WindowExceeded
+        );
     }
 }
 
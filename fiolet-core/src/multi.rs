@@ -0,0 +1,300 @@
+// fiolet-core/src/multi.rs
+
+//! Multi-channel safety kernel.
+//!
+//! Evaluates `N` deviation channels per step, each against its own limit.
+//! All channels share ONE latch: the first channel (in index order) that
+//! exceeds its limit or is non-finite halts the whole kernel.
+//!
+//! Fixed-size, allocation-free, same invariants (I1–I6) as `SafetyKernel`.
+
+use crate::{check_limit, ConfigError, HaltCause, HaltReason, SafetyDecision};
+
+/// Maximum channel count exposed through the C ABI.
+pub const FIOLET_MAX_CHANNELS: usize = 16;
+
+/// Multi-channel kernel instance used behind the C ABI.
+pub type AbiMultiChannelKernel = MultiChannelKernel<FIOLET_MAX_CHANNELS>;
+
+/// Multi-channel safety kernel state.
+///
+/// Only the first `active` channels are evaluated. Kernels built in Rust
+/// always have `active == N`; the C ABI may configure fewer channels.
+#[repr(C)]
+pub struct MultiChannelKernel<const N: usize> {
+    limits: [f32; N],
+    active: usize,
+    halted: bool,
+    steps: u64,
+    cause: HaltCause,
+}
+
+/// Check every limit; report the first invalid channel.
+///
+/// Zero channels would evaluate nothing and never halt: rejected.
+const fn check_limits(limits: &[f32]) -> Result<(), (ConfigError, u32)> {
+    if limits.is_empty() {
+        return Err((ConfigError::InvalidChannelCount, HaltCause::NO_CHANNEL));
+    }
+    let mut channel = 0;
+    while channel < limits.len() {
+        if let Err(error) = check_limit(limits[channel]) {
+            return Err((error, channel as u32));
+        }
+        channel += 1;
+    }
+    Ok(())
+}
+
+impl<const N: usize> MultiChannelKernel<N> {
+    /// Create a new kernel with one absolute limit per channel.
+    ///
+    /// Fail-closed: zero channels, or any NaN, infinite or negative limit,
+    /// yields a kernel that starts halted (`HaltReason::InvalidConfig`,
+    /// offending channel).
+    pub const fn new(limits: [f32; N]) -> Self {
+        let (halted, channel) = match check_limits(&limits) {
+            Ok(()) => (false, HaltCause::NO_CHANNEL),
+            Err((_, channel)) => (true, channel),
+        };
+
+        Self {
+            limits,
+            active: N,
+            halted,
+            steps: 0,
+            // Only observable once `halted` is set.
+            cause: HaltCause::new(HaltReason::InvalidConfig, f32::NAN, 0).on_channel(channel),
+        }
+    }
+
+    /// Create a new kernel, rejecting invalid limits.
+    pub const fn try_new(limits: [f32; N]) -> Result<Self, ConfigError> {
+        match check_limits(&limits) {
+            Ok(()) => Ok(Self::new(limits)),
+            Err((error, _)) => Err(error),
+        }
+    }
+
+    /// Create a kernel from a runtime list of limits.
+    ///
+    /// Fail-closed: no limits, more than `N` limits or any invalid limit
+    /// yields an already-halted kernel.
+    pub fn from_limits(limits: &[f32]) -> Self {
+        let mut kernel = Self::new([0.0; N]);
+
+        if limits.len() > N {
+            kernel.latch(HaltCause::new(HaltReason::InvalidConfig, f32::NAN, 0));
+            return kernel;
+        }
+
+        if let Err((_, channel)) = check_limits(limits) {
+            let value = limits.get(channel as usize).copied().unwrap_or(f32::NAN);
+            let cause = HaltCause::new(HaltReason::InvalidConfig, value, 0);
+            kernel.latch(cause.on_channel(channel));
+            return kernel;
+        }
+
+        kernel.limits[..limits.len()].copy_from_slice(limits);
+        kernel.active = limits.len();
+        kernel
+    }
+
+    /// Runtime counterpart of `try_new`.
+    pub fn try_from_limits(limits: &[f32]) -> Result<Self, ConfigError> {
+        if limits.len() > N {
+            return Err(ConfigError::InvalidChannelCount);
+        }
+        match check_limits(limits) {
+            Ok(()) => Ok(Self::from_limits(limits)),
+            Err((error, _)) => Err(error),
+        }
+    }
+
+    /// Evaluate one deviation per channel.
+    pub fn evaluate(&mut self, deviations: &[f32; N]) -> SafetyDecision {
+        self.evaluate_channels(deviations)
+    }
+
+    /// Evaluate a runtime slice of deviations.
+    ///
+    /// Fail-closed: a slice whose length differs from the configured
+    /// channel count latches halt without attributing a channel.
+    pub fn evaluate_channels(&mut self, deviations: &[f32]) -> SafetyDecision {
+        if self.halted {
+            return SafetyDecision::AtomicHalt;
+        }
+
+        if deviations.len() != self.active {
+            return self.latch_invalid_input();
+        }
+
+        let step = self.steps;
+        self.steps = self.steps.saturating_add(1);
+
+        for (channel, (&deviation, &limit)) in deviations.iter().zip(self.limits.iter()).enumerate()
+        {
+            let reason = if !deviation.is_finite() {
+                HaltReason::NonFinite
+            } else if deviation > limit {
+                HaltReason::ThresholdExceeded
+            } else {
+                continue;
+            };
+
+            let cause = HaltCause::new(reason, deviation, step).on_channel(channel as u32);
+            return self.latch(cause);
+        }
+
+        SafetyDecision::Continue
+    }
+
+    /// Latch halt on malformed input, without attributing a channel.
+    pub(crate) fn latch_invalid_input(&mut self) -> SafetyDecision {
+        self.latch(HaltCause::new(
+            HaltReason::InvalidInput,
+            f32::NAN,
+            self.steps,
+        ))
+    }
+
+    /// Latch halt, recording its cause. The first cause is never replaced.
+    fn latch(&mut self, cause: HaltCause) -> SafetyDecision {
+        if !self.halted {
+            self.halted = true;
+            self.cause = cause;
+        }
+        SafetyDecision::AtomicHalt
+    }
+
+    /// Query whether the kernel is already halted.
+    pub const fn is_halted(&self) -> bool {
+        self.halted
+    }
+
+    /// Cause recorded at latch time, or `None` while running.
+    pub const fn halt_cause(&self) -> Option<HaltCause> {
+        if self.halted {
+            Some(self.cause)
+        } else {
+            None
+        }
+    }
+
+    /// Index of the channel that latched the halt, if any.
+    pub fn tripped_channel(&self) -> Option<usize> {
+        self.halt_cause()
+            .and_then(|cause| cause.channel())
+            .map(|channel| channel as usize)
+    }
+
+    /// Number of channels evaluated per step.
+    pub const fn channels(&self) -> usize {
+        self.active
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn first_offending_channel_is_reported() {
+        let mut kernel = MultiChannelKernel::new([1.0, 0.5, 2.0]);
+
+        assert_eq!(kernel.evaluate(&[0.9, 0.4, 1.9]), SafetyDecision::Continue);
+        assert_eq!(kernel.tripped_channel(), None);
+
+        assert_eq!(
+            kernel.evaluate(&[0.0, 0.6, 3.0]),
+            SafetyDecision::AtomicHalt
+        );
+        assert_eq!(kernel.tripped_channel(), Some(1));
+
+        assert_eq!(
+            kernel.evaluate(&[0.0, 0.0, 0.0]),
+            SafetyDecision::AtomicHalt
+        );
+        assert_eq!(kernel.tripped_channel(), Some(1));
+
+        let cause = kernel.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::ThresholdExceeded);
+        assert_eq!(cause.value(), 0.6);
+        assert_eq!(cause.step(), 1);
+    }
+
+    #[test]
+    fn non_finite_channel_halts() {
+        let mut kernel = MultiChannelKernel::new([1.0, 1.0]);
+
+        assert_eq!(
+            kernel.evaluate(&[0.0, f32::NAN]),
+            SafetyDecision::AtomicHalt
+        );
+        assert!(kernel.is_halted());
+        assert_eq!(kernel.tripped_channel(), Some(1));
+    }
+
+    #[test]
+    fn runtime_channel_count_is_enforced() {
+        let mut kernel = AbiMultiChannelKernel::from_limits(&[1.0, 1.0]);
+        assert_eq!(kernel.channels(), 2);
+
+        assert_eq!(
+            kernel.evaluate_channels(&[0.5, 0.5]),
+            SafetyDecision::Continue
+        );
+        assert_eq!(kernel.evaluate_channels(&[0.5]), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.tripped_channel(), None);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::InvalidInput
+        );
+
+        let oversized = MultiChannelKernel::<2>::from_limits(&[1.0, 1.0, 1.0]);
+        assert!(oversized.is_halted());
+        assert_eq!(
+            MultiChannelKernel::<2>::try_from_limits(&[1.0, 1.0, 1.0]).err(),
+            Some(ConfigError::InvalidChannelCount)
+        );
+    }
+
+    #[test]
+    fn zero_channels_start_halted() {
+        let mut kernel = AbiMultiChannelKernel::from_limits(&[]);
+        assert!(kernel.is_halted());
+        assert_eq!(kernel.evaluate_channels(&[]), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::InvalidConfig
+        );
+        assert_eq!(
+            AbiMultiChannelKernel::try_from_limits(&[]).err(),
+            Some(ConfigError::InvalidChannelCount)
+        );
+
+        assert!(MultiChannelKernel::<0>::new([]).is_halted());
+        assert!(MultiChannelKernel::<0>::try_new([]).is_err());
+    }
+
+    #[test]
+    fn invalid_limit_starts_halted() {
+        let mut kernel = MultiChannelKernel::new([1.0, f32::NAN, -1.0]);
+
+        assert!(kernel.is_halted());
+        assert_eq!(kernel.evaluate(&[0.0; 3]), SafetyDecision::AtomicHalt);
+        assert_eq!(kernel.tripped_channel(), Some(1));
+        assert_eq!(
+            kernel.halt_cause().unwrap().reason(),
+            HaltReason::InvalidConfig
+        );
+        assert_eq!(
+            MultiChannelKernel::try_new([1.0, -1.0]).err(),
+            Some(ConfigError::NegativeLimit)
+        );
+    }
+}
@@ -0,0 +1,269 @@
+// fiolet-core/src/tmr.rs
+
+//! Triple-modular-redundant safety kernel.
+//!
+//! Three independent `SafetyKernel` replicas receive every deviation.
+//! The result is `Continue` only when all three return `Continue`;
+//! unanimous halts latch with the replicas' own cause, and ANY
+//! disagreement latches `HaltReason::ReplicaDisagreement`, naming the
+//! dissenting replica. There is no majority masking: a divergent
+//! replica is evidence of a fault, and faults fail closed.
+//!
+//! Fixed-size, allocation-free, same invariants (I1–I6) as `SafetyKernel`.
+
+use crate::integrity::LatchWord;
+use crate::{ConfigError, HaltCause, HaltReason, SafetyConfig, SafetyDecision, SafetyKernel};
+
+/// Number of replicas.
+pub const TMR_REPLICAS: usize = 3;
+
+/// Triple-modular-redundant kernel state.
+#[repr(C)]
+pub struct TmrSafetyKernel {
+    replicas: [SafetyKernel; TMR_REPLICAS],
+    latch: LatchWord,
+    steps: u64,
+    cause: HaltCause,
+}
+
+impl TmrSafetyKernel {
+    /// Create three replicas from one configuration.
+    ///
+    /// Fail-closed: an invalid configuration yields a kernel that starts
+    /// halted with `HaltReason::InvalidConfig`.
+    pub const fn new(config: SafetyConfig) -> Self {
+        let replicas = [
+            SafetyKernel::new(config),
+            SafetyKernel::new(config),
+            SafetyKernel::new(config),
+        ];
+        let halted = replicas[0].is_halted();
+
+        Self {
+            replicas,
+            latch: if halted {
+                LatchWord::HALTED
+            } else {
+                LatchWord::RUNNING
+            },
+            steps: 0,
+            // Only observable once halted.
+            cause: HaltCause::new(HaltReason::InvalidConfig, f32::NAN, 0),
+        }
+    }
+
+    /// Create a new kernel, rejecting an invalid configuration.
+    pub const fn try_new(config: SafetyConfig) -> Result<Self, ConfigError> {
+        match config.validate() {
+            Ok(()) => Ok(Self::new(config)),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Evaluate a deviation on every replica and vote.
+    pub fn evaluate(&mut self, deviation: f32) -> SafetyDecision {
+        if !self.latch.is_running() {
+            return self.halt_on_corrupt_latch();
+        }
+
+        let step = self.steps;
+        self.steps = self.steps.saturating_add(1);
+
+        let mut decisions = [SafetyDecision::AtomicHalt; TMR_REPLICAS];
+        for (decision, replica) in decisions.iter_mut().zip(self.replicas.iter_mut()) {
+            *decision = replica.evaluate(deviation).decision();
+        }
+
+        match dissenter(&decisions) {
+            Some(replica) => self.latch(
+                HaltCause::new(HaltReason::ReplicaDisagreement, deviation, step)
+                    .on_channel(replica as u32),
+            ),
+            None if decisions[0] == SafetyDecision::Continue => SafetyDecision::Continue,
+            // Unanimous halt: every replica recorded the same cause.
+            None => self.latch(self.replicas[0].halt_cause().unwrap_or(HaltCause::new(
+                HaltReason::StateCorrupted,
+                deviation,
+                step,
+            ))),
+        }
+    }
+
+    /// Latch halt, recording its cause. The first cause is never replaced.
+    fn latch(&mut self, cause: HaltCause) -> SafetyDecision {
+        if self.latch.is_running() {
+            self.latch = LatchWord::HALTED;
+            self.cause = cause;
+        }
+        SafetyDecision::AtomicHalt
+    }
+
+    /// Latch word neither RUNNING nor HALTED: record the corruption.
+    fn halt_on_corrupt_latch(&mut self) -> SafetyDecision {
+        if !self.latch.is_latched() {
+            self.latch = LatchWord::HALTED;
+            self.cause = HaltCause::new(HaltReason::StateCorrupted, f32::NAN, self.steps);
+        }
+        SafetyDecision::AtomicHalt
+    }
+
+    /// Query whether the kernel is halted.
+    ///
+    /// Also true if any replica reads as halted, so replicas that
+    /// diverge between evaluations (memory fault) are never masked.
+    pub const fn is_halted(&self) -> bool {
+        !self.latch.is_running()
+            || self.replicas[0].is_halted()
+            || self.replicas[1].is_halted()
+            || self.replicas[2].is_halted()
+    }
+
+    /// Cause recorded at latch time, or `None` while running.
+    ///
+    /// Always `Some` when [`is_halted`](Self::is_halted) holds: replicas
+    /// that all read as halted before the latch report the first
+    /// replica's cause, or `StateCorrupted` if it has none.
+    pub const fn halt_cause(&self) -> Option<HaltCause> {
+        if self.latch.is_latched() {
+            Some(self.cause)
+        } else if self.latch.is_running() {
+            match dissenter_halted(&self.replicas) {
+                Some(replica) => Some(
+                    HaltCause::new(HaltReason::ReplicaDisagreement, f32::NAN, self.steps)
+                        .on_channel(replica as u32),
+                ),
+                None if self.is_halted() => match self.replicas[0].halt_cause() {
+                    Some(cause) => Some(cause),
+                    None => Some(HaltCause::new(
+                        HaltReason::StateCorrupted,
+                        f32::NAN,
+                        self.steps,
+                    )),
+                },
+                None => None,
+            }
+        } else {
+            Some(HaltCause::new(
+                HaltReason::StateCorrupted,
+                f32::NAN,
+                self.steps,
+            ))
+        }
+    }
+
+    /// Number of evaluations performed while running.
+    pub const fn steps(&self) -> u64 {
+        self.steps
+    }
+}
+
+/// Index of the replica that disagrees with the other two, if any.
+///
+/// With three binary votes, any disagreement leaves exactly one
+/// replica in the minority.
+const fn dissenter(decisions: &[SafetyDecision; TMR_REPLICAS]) -> Option<usize> {
+    let [a, b, c] = *decisions;
+    if a as u8 == b as u8 && b as u8 == c as u8 {
+        None
+    } else if a as u8 == b as u8 {
+        Some(2)
+    } else if a as u8 == c as u8 {
+        Some(1)
+    } else {
+        Some(0)
+    }
+}
+
+/// Replica whose halted state disagrees with the others, if any.
+const fn dissenter_halted(replicas: &[SafetyKernel; TMR_REPLICAS]) -> Option<usize> {
+    const fn vote(halted: bool) -> SafetyDecision {
+        if halted {
+            SafetyDecision::AtomicHalt
+        } else {
+            SafetyDecision::Continue
+        }
+    }
+
+    dissenter(&[
+        vote(replicas[0].is_halted()),
+        vote(replicas[1].is_halted()),
+        vote(replicas[2].is_halted()),
+    ])
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unanimous_replicas_behave_like_one_kernel() {
+        let config = SafetyConfig::new(0.5).with_slope_limit(0.3);
+        let mut tmr = TmrSafetyKernel::new(config);
+        let mut single = SafetyKernel::new(config);
+
+        for deviation in [0.1, 0.3, 0.45, 0.9, 0.0] {
+            assert_eq!(single.evaluate(deviation), tmr.evaluate(deviation));
+        }
+        assert!(tmr.is_halted());
+        assert_eq!(tmr.halt_cause(), single.halt_cause());
+    }
+
+    #[test]
+    fn disagreement_latches_distinct_cause() {
+        let mut tmr = TmrSafetyKernel::new(SafetyConfig::new(1.0));
+        assert_eq!(tmr.evaluate(0.5), SafetyDecision::Continue);
+
+        // Corrupt one replica's limit (as a memory fault would).
+        tmr.replicas[1] = SafetyKernel::new(SafetyConfig::new(0.1));
+
+        assert_eq!(tmr.evaluate(0.5), SafetyDecision::AtomicHalt);
+        assert_eq!(tmr.evaluate(0.0), SafetyDecision::AtomicHalt);
+
+        let cause = tmr.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::ReplicaDisagreement);
+        assert_eq!(cause.channel(), Some(1));
+        assert_eq!(cause.step(), 1);
+    }
+
+    #[test]
+    fn diverged_replica_reads_as_halted() {
+        let mut tmr = TmrSafetyKernel::new(SafetyConfig::new(1.0));
+        tmr.replicas[2].evaluate(f32::NAN);
+
+        assert!(tmr.is_halted());
+        let cause = tmr.halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::ReplicaDisagreement);
+        assert_eq!(cause.channel(), Some(2));
+
+        assert_eq!(tmr.evaluate(0.0), SafetyDecision::AtomicHalt);
+        assert!(tmr.is_halted());
+    }
+
+    #[test]
+    fn unanimously_halted_replicas_report_a_cause() {
+        let mut tmr = TmrSafetyKernel::new(SafetyConfig::new(1.0));
+        for replica in &mut tmr.replicas {
+            replica.evaluate(f32::NAN);
+        }
+
+        assert!(tmr.is_halted());
+        assert_eq!(tmr.halt_cause().unwrap().reason(), HaltReason::NonFinite);
+    }
+
+    #[test]
+    fn invalid_config_starts_halted() {
+        let mut tmr = TmrSafetyKernel::new(SafetyConfig::new(f32::NAN));
+
+        assert!(tmr.is_halted());
+        assert_eq!(tmr.evaluate(0.0), SafetyDecision::AtomicHalt);
+        assert_eq!(
+            tmr.halt_cause().unwrap().reason(),
+            HaltReason::InvalidConfig
+        );
+        assert!(TmrSafetyKernel::try_new(SafetyConfig::new(-1.0)).is_err());
+    }
+}
@@ -0,0 +1,194 @@
+// fiolet-core/src/typestate.rs
+
+//! Typestate kernel API.
+//!
+//! `TypedKernel<Running>` and `TypedKernel<Halted>` carry the halt latch
+//! in the type. Evaluation consumes a running kernel and hands back
+//! either a running one with the step's `ContinueToken` (for
+//! `OutputGate::release`) or a halted one; a halted kernel has no
+//! `evaluate`, so code holding one cannot even express continuation.
+//! Neither type is `Copy` or `Clone`, so a kernel cannot be duplicated
+//! to probe a value on a throwaway copy.
+//!
+//! ```compile_fail
+//! use fiolet_core::{SafetyConfig, Transition, TypedKernel};
+//!
+//! if let Transition::Halted(kernel) = TypedKernel::start(SafetyConfig::new(f32::NAN)) {
+//!     kernel.evaluate(0.0); // no such method on TypedKernel<Halted>
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use fiolet_core::SafetyKernel;
+//!
+//! fn probe(kernel: &SafetyKernel) -> SafetyKernel {
+//!     *kernel // SafetyKernel is not Copy
+//! }
+//! ```
+//!
+//! The C ABI is unaffected: kernels behind it live in the handle pool
+//! and are never copied.
+
+use core::marker::PhantomData;
+
+use crate::{
+    ContinueToken, HaltCause, HaltReason, SafetyConfig, SafetyDecision, SafetyKernel, Verdict,
+};
+
+/// Kernel may evaluate.
+pub enum Running {}
+
+/// Kernel is latched; permanently.
+pub enum Halted {}
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Running {}
+    impl Sealed for super::Halted {}
+}
+
+/// Typestate marker: `Running` or `Halted`.
+pub trait KernelState: sealed::Sealed {}
+
+impl KernelState for Running {}
+impl KernelState for Halted {}
+
+/// `SafetyKernel` whose halt state is part of its type.
+pub struct TypedKernel<S: KernelState> {
+    kernel: SafetyKernel,
+    state: PhantomData<S>,
+}
+
+/// Kernel after creation or evaluation.
+pub enum Transition {
+    /// A valid configuration started; nothing evaluated yet.
+    Running(TypedKernel<Running>),
+    /// The evaluation returned `Continue`; the token authorizes its output.
+    Continue(TypedKernel<Running>, ContinueToken),
+    /// The kernel latched (now or earlier).
+    Halted(TypedKernel<Halted>),
+}
+
+impl Transition {
+    fn from_kernel(kernel: SafetyKernel) -> Self {
+        if kernel.is_halted() {
+            Transition::Halted(TypedKernel::wrap(kernel))
+        } else {
+            Transition::Running(TypedKernel::wrap(kernel))
+        }
+    }
+
+    /// Decision this transition corresponds to.
+    pub const fn decision(&self) -> SafetyDecision {
+        match self {
+            Transition::Running(_) | Transition::Continue(..) => SafetyDecision::Continue,
+            Transition::Halted(_) => SafetyDecision::AtomicHalt,
+        }
+    }
+}
+
+impl<S: KernelState> TypedKernel<S> {
+    const fn wrap(kernel: SafetyKernel) -> Self {
+        Self {
+            kernel,
+            state: PhantomData,
+        }
+    }
+
+    /// Number of evaluations performed while running.
+    pub const fn steps(&self) -> u64 {
+        self.kernel.steps()
+    }
+}
+
+impl TypedKernel<Running> {
+    /// Create a kernel. Fail-closed: an invalid configuration yields
+    /// `Transition::Halted` with `HaltReason::InvalidConfig`.
+    pub fn start(config: SafetyConfig) -> Transition {
+        Transition::from_kernel(SafetyKernel::new(config))
+    }
+
+    /// Evaluate a deviation, consuming the running kernel.
+    pub fn evaluate(mut self, deviation: f32) -> Transition {
+        match self.kernel.evaluate(deviation) {
+            Verdict::Continue(token) => Transition::Continue(self, token),
+            Verdict::AtomicHalt => Transition::Halted(TypedKernel::wrap(self.kernel)),
+        }
+    }
+}
+
+impl TypedKernel<Halted> {
+    /// Cause recorded at latch time.
+    pub const fn halt_cause(&self) -> HaltCause {
+        match self.kernel.halt_cause() {
+            Some(cause) => cause,
+            // Unreachable for a latched kernel; fail toward corruption.
+            None => HaltCause::new(HaltReason::StateCorrupted, f32::NAN, self.kernel.steps()),
+        }
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::OutputGate;
+
+    #[test]
+    fn running_kernel_moves_to_halted_exactly_once() {
+        let Transition::Running(kernel) = TypedKernel::start(SafetyConfig::new(1.0)) else {
+            panic!("valid configuration starts running");
+        };
+        let Transition::Continue(kernel, _token) = kernel.evaluate(0.5) else {
+            panic!("0.5 is within the limit");
+        };
+
+        let transition = kernel.evaluate(1.5);
+        assert_eq!(transition.decision(), SafetyDecision::AtomicHalt);
+        let Transition::Halted(halted) = transition else {
+            unreachable!();
+        };
+        assert_eq!(halted.halt_cause().reason(), HaltReason::ThresholdExceeded);
+        assert_eq!(halted.halt_cause().step(), 1);
+        assert_eq!(halted.steps(), 2);
+    }
+
+    #[test]
+    fn continue_token_releases_gated_output() {
+        let Transition::Running(kernel) = TypedKernel::start(SafetyConfig::new(1.0)) else {
+            panic!("valid configuration starts running");
+        };
+        let mut gate = OutputGate::<8>::new();
+        gate.write(b"ok").unwrap();
+
+        let transition = kernel.evaluate(0.5);
+        assert_eq!(transition.decision(), SafetyDecision::Continue);
+        let Transition::Continue(kernel, token) = transition else {
+            panic!("0.5 is within the limit");
+        };
+        assert_eq!(gate.release(token), Ok(&b"ok"[..]));
+
+        gate.write(b"no").unwrap();
+        let Transition::Halted(_) = kernel.evaluate(1.5) else {
+            panic!("1.5 exceeds the limit");
+        };
+        gate.seal();
+        assert_eq!(gate.pending(), 0);
+    }
+
+    #[test]
+    fn invalid_config_starts_halted() {
+        let transition = TypedKernel::start(SafetyConfig::new(-1.0));
+        match transition {
+            Transition::Halted(halted) => {
+                assert_eq!(halted.halt_cause().reason(), HaltReason::InvalidConfig)
+            }
+            Transition::Running(_) | Transition::Continue(..) => {
+                panic!("invalid configuration must not run")
+            }
+        }
+    }
+}
@@ -0,0 +1,190 @@
+// fiolet-core/src/version.rs
+
+//! ABI version, capability bits and build fingerprint.
+//!
+//! Lets a host check at runtime which kernel it is linked against, and
+//! lets an auditor prove which build was running. Everything here is a
+//! compile-time constant: no state, no allocation, no I/O.
+//!
+//! The fingerprint is FNV-1a (64-bit) over the crate version, the ABI
+//! version, the enabled features and a hash of the build configuration
+//! (target, profile, panic strategy, ABI layout and pool sizes). It
+//! identifies a build; it is NOT a cryptographic commitment.
+
+use crate::integrity::{fnv1a, fnv1a_start, fnv1a_u64};
+use crate::multi::FIOLET_MAX_CHANNELS;
+use crate::{
+    AbiSafetyConfig, AbiSafetyConfigV2, HaltCause, SafetyKernel, FIOLET_MAX_FIXED_KERNELS,
+    FIOLET_MAX_KERNELS, FIOLET_MAX_MULTI_KERNELS, FIOLET_MAX_TMR_KERNELS, WINDOW_CAPACITY,
+};
+
+/// Incremented on incompatible ABI changes.
+pub const FIOLET_ABI_VERSION_MAJOR: u32 = 1;
+
+/// Incremented on backwards-compatible ABI additions.
+pub const FIOLET_ABI_VERSION_MINOR: u32 = 4;
+
+/// `(major << 16) | minor`.
+pub const FIOLET_ABI_VERSION: u32 = (FIOLET_ABI_VERSION_MAJOR << 16) | FIOLET_ABI_VERSION_MINOR;
+
+/// Crate version, NUL-terminated for the C ABI.
+pub const FIOLET_CRATE_VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+// ------------------------------------------------------------
+// Capabilities (what the linked kernel can do)
+// ------------------------------------------------------------
+
+/// Opaque-handle ABI with `*_free`.
+pub const FIOLET_CAP_HANDLES: u64 = 1 << 0;
+/// `fiolet_kernel_new_with_config` / `fiolet_config_validate`.
+pub const FIOLET_CAP_CONFIG: u64 = 1 << 1;
+/// `SafetyConfig::max_steps`.
+pub const FIOLET_CAP_STEP_BUDGET: u64 = 1 << 2;
+/// `SafetyConfig::window`.
+pub const FIOLET_CAP_WINDOW: u64 = 1 << 3;
+/// `SafetyConfig::slope_limit`.
+pub const FIOLET_CAP_SLOPE_LIMIT: u64 = 1 << 4;
+/// `fiolet_kernel_evaluate_slice`.
+pub const FIOLET_CAP_BATCH: u64 = 1 << 5;
+/// `fiolet_*_halt_cause`.
+pub const FIOLET_CAP_HALT_CAUSE: u64 = 1 << 6;
+/// `fiolet_multi_kernel_*`.
+pub const FIOLET_CAP_MULTI_CHANNEL: u64 = 1 << 7;
+/// `fiolet_fixed_kernel_*`.
+pub const FIOLET_CAP_FIXED_POINT: u64 = 1 << 8;
+/// Self-checking `FioletKernel` state (`HaltReason::StateCorrupted`).
+pub const FIOLET_CAP_SELF_CHECK: u64 = 1 << 9;
+/// `fiolet_tmr_kernel_*`.
+pub const FIOLET_CAP_TMR: u64 = 1 << 10;
+/// `fiolet_kernel_set_halt_callback`.
+pub const FIOLET_CAP_HALT_CALLBACK: u64 = 1 << 11;
+/// `fiolet_*_v2` constructors taking `FioletSafetyConfigV2` (baseline band).
+pub const FIOLET_CAP_BASELINE_BAND: u64 = 1 << 12;
+
+/// Capabilities of this build.
+pub const FIOLET_CAPABILITIES: u64 = FIOLET_CAP_HANDLES
+    | FIOLET_CAP_CONFIG
+    | FIOLET_CAP_STEP_BUDGET
+    | FIOLET_CAP_WINDOW
+    | FIOLET_CAP_SLOPE_LIMIT
+    | FIOLET_CAP_BATCH
+    | FIOLET_CAP_HALT_CAUSE
+    | FIOLET_CAP_MULTI_CHANNEL
+    | FIOLET_CAP_FIXED_POINT
+    | FIOLET_CAP_SELF_CHECK
+    | FIOLET_CAP_TMR
+    | FIOLET_CAP_HALT_CALLBACK
+    | FIOLET_CAP_BASELINE_BAND;
+
+// ------------------------------------------------------------
+// Cargo features compiled in
+// ------------------------------------------------------------
+
+/// `kernel` feature.
+pub const FIOLET_FEATURE_KERNEL: u32 = 1 << 0;
+/// `std` feature (test / conformance builds only).
+pub const FIOLET_FEATURE_STD: u32 = 1 << 1;
+
+/// Features enabled in this build.
+pub const FIOLET_FEATURES: u32 = flag(cfg!(feature = "kernel"), FIOLET_FEATURE_KERNEL)
+    | flag(cfg!(feature = "std"), FIOLET_FEATURE_STD);
+
+// ------------------------------------------------------------
+// Build fingerprint
+// ------------------------------------------------------------
+
+/// Build provenance record.
+///
+/// Plain data only.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct BuildInfo {
+    /// `FIOLET_ABI_VERSION`.
+    pub abi_version: u32,
+    /// `FIOLET_FEATURE_*` bits.
+    pub features: u32,
+    /// `FIOLET_CAP_*` bits.
+    pub capabilities: u64,
+    /// Hash of target, profile, panic strategy and ABI layout.
+    pub config_hash: u64,
+    /// Hash of crate version, ABI version, features and `config_hash`.
+    pub fingerprint: u64,
+}
+
+/// Provenance of this build.
+pub const BUILD_INFO: BuildInfo = BuildInfo {
+    abi_version: FIOLET_ABI_VERSION,
+    features: FIOLET_FEATURES,
+    capabilities: FIOLET_CAPABILITIES,
+    config_hash: CONFIG_HASH,
+    fingerprint: FINGERPRINT,
+};
+
+const fn flag(enabled: bool, bit: u32) -> u32 {
+    if enabled {
+        bit
+    } else {
+        0
+    }
+}
+
+/// Target and profile properties that change generated code.
+const TARGET_BITS: u32 = flag(cfg!(target_endian = "little"), 1 << 0)
+    | flag(cfg!(debug_assertions), 1 << 1)
+    | flag(cfg!(panic = "abort"), 1 << 2)
+    | flag(cfg!(target_arch = "x86"), 1 << 8)
+    | flag(cfg!(target_arch = "x86_64"), 1 << 9)
+    | flag(cfg!(target_arch = "arm"), 1 << 10)
+    | flag(cfg!(target_arch = "aarch64"), 1 << 11)
+    | flag(cfg!(target_arch = "riscv32"), 1 << 12)
+    | flag(cfg!(target_arch = "riscv64"), 1 << 13)
+    | flag(cfg!(target_feature = "hard-float"), 1 << 16)
+    | flag(cfg!(target_feature = "sse2"), 1 << 17)
+    | flag(cfg!(target_feature = "fma"), 1 << 18);
+
+const CONFIG_HASH: u64 = {
+    let mut hash = fnv1a_start(b"fiolet-config");
+    hash = fnv1a_u64(hash, TARGET_BITS as u64);
+    hash = fnv1a_u64(hash, core::mem::size_of::<usize>() as u64);
+    hash = fnv1a_u64(hash, core::mem::align_of::<u64>() as u64);
+    hash = fnv1a_u64(hash, core::mem::size_of::<AbiSafetyConfig>() as u64);
+    hash = fnv1a_u64(hash, core::mem::size_of::<AbiSafetyConfigV2>() as u64);
+    hash = fnv1a_u64(hash, core::mem::size_of::<SafetyKernel>() as u64);
+    hash = fnv1a_u64(hash, core::mem::size_of::<HaltCause>() as u64);
+    hash = fnv1a_u64(hash, WINDOW_CAPACITY as u64);
+    hash = fnv1a_u64(hash, FIOLET_MAX_CHANNELS as u64);
+    hash = fnv1a_u64(hash, FIOLET_MAX_KERNELS as u64);
+    hash = fnv1a_u64(hash, FIOLET_MAX_MULTI_KERNELS as u64);
+    hash = fnv1a_u64(hash, FIOLET_MAX_FIXED_KERNELS as u64);
+    fnv1a_u64(hash, FIOLET_MAX_TMR_KERNELS as u64)
+};
+
+const FINGERPRINT: u64 = {
+    let mut hash = fnv1a_start(b"fiolet-build");
+    hash = fnv1a(hash, FIOLET_CRATE_VERSION.as_bytes());
+    hash = fnv1a_u64(hash, FIOLET_ABI_VERSION as u64);
+    hash = fnv1a_u64(hash, FIOLET_FEATURES as u64);
+    hash = fnv1a_u64(hash, FIOLET_CAPABILITIES);
+    fnv1a_u64(hash, CONFIG_HASH)
+};
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_info_is_self_consistent() {
+        assert_eq!(BUILD_INFO.abi_version >> 16, FIOLET_ABI_VERSION_MAJOR);
+        assert_eq!(
+            BUILD_INFO.features & FIOLET_FEATURE_STD != 0,
+            cfg!(feature = "std")
+        );
+        assert_ne!(BUILD_INFO.config_hash, BUILD_INFO.fingerprint);
+        assert!(FIOLET_CRATE_VERSION.starts_with(env!("CARGO_PKG_VERSION")));
+        assert!(FIOLET_CRATE_VERSION.ends_with('\0'));
+    }
+}
@@ -0,0 +1,148 @@
+// fiolet-core/src/window.rs
+
+//! K-of-N windowed trigger.
+//!
+//! Tracks whether each of the last N deviations exceeded a soft limit
+//! and fires once K of them did. The window is a fixed-size ring of
+//! bits (one `u64`), so there is no heap and no adaptivity: the soft
+//! limit, K and N are frozen in `SafetyConfig`.
+
+use crate::integrity::{fnv1a, fnv1a_u64};
+use crate::{check_limit, ConfigError, Validate};
+
+/// Largest supported window length N.
+pub const WINDOW_CAPACITY: u8 = 64;
+
+/// K-of-N window configuration.
+///
+/// Plain data only. `n == 0` disables the window.
+#[repr(C)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct WindowConfig {
+    /// Deviations strictly above this value count as exceedances.
+    pub soft_limit: f32,
+    /// Exceedances within the window required to latch halt.
+    pub k: u8,
+    /// Window length, at most `WINDOW_CAPACITY`.
+    pub n: u8,
+}
+
+impl WindowConfig {
+    /// Window trigger switched off.
+    pub const DISABLED: Self = Self {
+        soft_limit: 0.0,
+        k: 0,
+        n: 0,
+    };
+
+    /// Latch when `k` of the last `n` deviations exceed `soft_limit`.
+    pub const fn new(soft_limit: f32, k: u8, n: u8) -> Self {
+        Self { soft_limit, k, n }
+    }
+
+    /// Whether the window participates in evaluation.
+    pub const fn is_enabled(&self) -> bool {
+        self.n != 0
+    }
+
+    /// A disabled window is always valid. An enabled one needs
+    /// `1 <= k <= n <= WINDOW_CAPACITY` and a finite, non-negative soft limit.
+    pub const fn validate(&self) -> Result<(), ConfigError> {
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        if self.n > WINDOW_CAPACITY || self.k == 0 || self.k > self.n {
+            return Err(ConfigError::InvalidWindow);
+        }
+        if check_limit(self.soft_limit).is_err() {
+            return Err(ConfigError::InvalidWindow);
+        }
+        Ok(())
+    }
+}
+
+impl Validate for WindowConfig {
+    fn validate(&self) -> Result<(), ConfigError> {
+        WindowConfig::validate(self)
+    }
+}
+
+/// Ring of the last N exceedance flags.
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub(crate) struct WindowState {
+    slots: u64,
+    head: u8,
+}
+
+impl WindowState {
+    pub(crate) const fn new() -> Self {
+        Self { slots: 0, head: 0 }
+    }
+
+    /// Fold the ring into a state checksum.
+    pub(crate) const fn seal(&self, hash: u64) -> u64 {
+        fnv1a(fnv1a_u64(hash, self.slots), &[self.head])
+    }
+
+    /// Record one deviation; returns true once K of the last N exceeded.
+    pub(crate) fn record(&mut self, config: &WindowConfig, deviation: f32) -> bool {
+        let n = config.n.min(WINDOW_CAPACITY);
+        if n == 0 {
+            return false;
+        }
+
+        let bit = 1u64 << self.head;
+        if deviation > config.soft_limit {
+            self.slots |= bit;
+        } else {
+            self.slots &= !bit;
+        }
+        self.head = (self.head + 1) % n;
+
+        self.slots.count_ones() >= u32::from(config.k)
+    }
+}
+
+// ============================================================
+// TESTS (STD ONLY — NOT PART OF TRUSTED KERNEL)
+// ============================================================
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fires_on_k_of_n() {
+        let config = WindowConfig::new(0.5, 3, 5);
+        let mut window = WindowState::new();
+
+        assert!(!window.record(&config, 0.6));
+        assert!(!window.record(&config, 0.1));
+        assert!(!window.record(&config, 0.7));
+        assert!(!window.record(&config, 0.2));
+        assert!(window.record(&config, 0.8));
+    }
+
+    #[test]
+    fn old_exceedances_leave_the_window() {
+        let config = WindowConfig::new(0.5, 2, 3);
+        let mut window = WindowState::new();
+
+        assert!(!window.record(&config, 0.9));
+        for _ in 0..3 {
+            assert!(!window.record(&config, 0.0));
+        }
+        assert!(!window.record(&config, 0.9));
+        assert!(window.record(&config, 0.9));
+    }
+
+    #[test]
+    fn disabled_window_never_fires() {
+        let mut window = WindowState::new();
+
+        for _ in 0..100 {
+            assert!(!window.record(&WindowConfig::DISABLED, f32::MAX));
+        }
+    }
+}
@@ -2,9 +2,7 @@ use fiolet_core::{SafetyKernel, SafetyConfig};
 
 #[test]
 fn atomic_halt_is_irreversible() {
-    let config = SafetyConfig {
-        deviation_limit: 0.5,
-    };
+    let config = SafetyConfig::new(0.5);
 
     let mut kernel = SafetyKernel::new(config);
 
@@ -0,0 +1,515 @@
+//! Tamper-evident audit log of kernel decisions (RFC-0003 §6:
+//! independent audit).
+//!
+//! `AuditedKernel` owns its `SafetyKernel`; the only way to evaluate is
+//! through it, and every evaluation is appended to the log BEFORE its
+//! verdict is returned. A verdict whose record could not be written is
+//! never handed out, so no `ContinueToken` exists without a record. A
+//! failed write poisons the `AuditedKernel`: the log may now have a gap,
+//! so every later call fails instead of evaluating.
+//!
+//! Each record carries the previous record's hash and its own,
+//! `SHA-256(previous hash || record body)`; the chain starts from the
+//! hash of the log header. The stored previous hash lets the verifier
+//! tell a damaged record from an intact one whose predecessor is gone.
+//!
+//! Every `interval` evaluations a checkpoint record is appended whose
+//! hash is the chain head at that point; publishing checkpoint digests
+//! elsewhere (ticket, second host) lets `verify` detect a log that was
+//! rewritten and re-hashed from the start, or cut short.
+//!
+//! `verify` also follows the kernel step counter: each evaluation record
+//! must advance it by exactly one (a halted kernel leaves it unchanged),
+//! so an evaluation left out before hashing shows up as a gap.
+//!
+//! Layout (little-endian):
+//!
+//! ```text
+//! header   12 bytes
+//!   0   magic     b"FAUD"
+//!   4   version   u16 (1)
+//!   6   reserved  u16 (0)
+//!   8   interval  u32  evaluations per checkpoint (0: manual only)
+//! record   90 bytes
+//!   0   kind      u8   1 evaluation, 2 checkpoint
+//!   1   seq       u64  0, 1, 2, … over all records
+//!   9   deviation u32  f32 bits (checkpoint: 0)
+//!   13  decision  u8   SafetyDecision code (checkpoint: 0)
+//!   14  reason    u32  HaltReason code once halted, else 0
+//!   18  steps     u64  kernel step counter after the evaluation
+//!   26  previous  [u8; 32]  hash of the preceding record (or header)
+//!   58  hash      [u8; 32]  SHA-256(previous || bytes 0..26)
+//! ```
+
+use core::fmt;
+use std::io::{self, Write};
+
+use fiolet_core::{SafetyConfig, SafetyDecision, SafetyKernel, Verdict};
+
+use crate::hash::{sha256, SHA256_LEN};
+
+const MAGIC: &[u8; 4] = b"FAUD";
+const VERSION: u16 = 1;
+const HEADER_LEN: usize = 12;
+const BODY_LEN: usize = 26;
+
+/// Size of one record in bytes.
+pub const RECORD_LEN: usize = BODY_LEN + 2 * SHA256_LEN;
+
+const PREVIOUS: core::ops::Range<usize> = BODY_LEN..BODY_LEN + SHA256_LEN;
+const HASH: core::ops::Range<usize> = BODY_LEN + SHA256_LEN..RECORD_LEN;
+
+const KIND_EVALUATION: u8 = 1;
+const KIND_CHECKPOINT: u8 = 2;
+
+/// Chain head at a checkpoint record; publish it to anchor the log.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct Checkpoint {
+    /// Sequence number of the checkpoint record.
+    pub seq: u64,
+    /// Hash of the checkpoint record (the chain head).
+    pub digest: [u8; SHA256_LEN],
+}
+
+/// `SafetyKernel` whose every evaluation is appended to a hash chain.
+pub struct AuditedKernel<W: Write> {
+    kernel: SafetyKernel,
+    sink: W,
+    head: [u8; SHA256_LEN],
+    seq: u64,
+    interval: u32,
+    since_checkpoint: u32,
+    /// A write failed; the log is no longer complete.
+    poisoned: bool,
+}
+
+impl<W: Write> AuditedKernel<W> {
+    /// Start a kernel for `config` and write the log header to `sink`.
+    ///
+    /// A checkpoint follows every `interval` evaluations; 0 disables
+    /// automatic checkpoints.
+    pub fn new(config: SafetyConfig, mut sink: W, interval: u32) -> io::Result<Self> {
+        let header = header(interval);
+        sink.write_all(&header)?;
+        Ok(Self {
+            kernel: SafetyKernel::new(config),
+            sink,
+            head: sha256(&[&header]),
+            seq: 0,
+            interval,
+            since_checkpoint: 0,
+            poisoned: false,
+        })
+    }
+
+    /// Evaluate a deviation and log it before returning the verdict.
+    ///
+    /// `Err`: the record (or its checkpoint) could not be written. The
+    /// kernel has still evaluated; the caller gets no verdict and must
+    /// treat the step as a halt. From then on the kernel is poisoned and
+    /// every call returns `Err` without evaluating.
+    pub fn evaluate(&mut self, deviation: f32) -> io::Result<Verdict> {
+        self.check_poisoned()?;
+        let verdict = self.kernel.evaluate(deviation);
+        let reason = self
+            .kernel
+            .halt_cause()
+            .map_or(0, |cause| cause.reason() as u32);
+
+        self.append(
+            KIND_EVALUATION,
+            deviation.to_bits(),
+            verdict.decision() as u8,
+            reason,
+        )?;
+
+        self.since_checkpoint += 1;
+        if self.since_checkpoint == self.interval {
+            self.checkpoint()?;
+        }
+        Ok(verdict)
+    }
+
+    /// Append a checkpoint record now and flush the sink.
+    pub fn checkpoint(&mut self) -> io::Result<Checkpoint> {
+        self.check_poisoned()?;
+        let reason = self
+            .kernel
+            .halt_cause()
+            .map_or(0, |cause| cause.reason() as u32);
+        let seq = self.seq;
+        self.append(KIND_CHECKPOINT, 0, 0, reason)?;
+        self.since_checkpoint = 0;
+        if let Err(error) = self.sink.flush() {
+            self.poisoned = true;
+            return Err(error);
+        }
+        Ok(Checkpoint {
+            seq,
+            digest: self.head,
+        })
+    }
+
+    /// Current chain head (hash of the last record, or of the header).
+    pub fn head(&self) -> [u8; SHA256_LEN] {
+        self.head
+    }
+
+    /// The wrapped kernel.
+    pub fn kernel(&self) -> &SafetyKernel {
+        &self.kernel
+    }
+
+    /// Whether a failed write has disabled this kernel.
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Stop logging and hand back the sink.
+    pub fn into_sink(self) -> W {
+        self.sink
+    }
+
+    fn append(&mut self, kind: u8, deviation: u32, decision: u8, reason: u32) -> io::Result<()> {
+        let mut record = [0u8; RECORD_LEN];
+        record[0] = kind;
+        record[1..9].copy_from_slice(&self.seq.to_le_bytes());
+        record[9..13].copy_from_slice(&deviation.to_le_bytes());
+        record[13] = decision;
+        record[14..18].copy_from_slice(&reason.to_le_bytes());
+        record[18..26].copy_from_slice(&self.kernel.steps().to_le_bytes());
+        let hash = sha256(&[&self.head, &record[..BODY_LEN]]);
+        record[PREVIOUS].copy_from_slice(&self.head);
+        record[HASH].copy_from_slice(&hash);
+
+        if let Err(error) = self.sink.write_all(&record) {
+            self.poisoned = true;
+            return Err(error);
+        }
+        self.head = hash;
+        self.seq += 1;
+        Ok(())
+    }
+
+    fn check_poisoned(&self) -> io::Result<()> {
+        if self.poisoned {
+            return Err(io::Error::other(
+                "audit log poisoned by an earlier write failure",
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn header(interval: u32) -> [u8; HEADER_LEN] {
+    let mut header = [0u8; HEADER_LEN];
+    header[0..4].copy_from_slice(MAGIC);
+    header[4..6].copy_from_slice(&VERSION.to_le_bytes());
+    header[8..12].copy_from_slice(&interval.to_le_bytes());
+    header
+}
+
+/// What is wrong at the first bad record.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Fault {
+    /// Header missing, unknown, or unsupported version.
+    Header,
+    /// Record contents or hash do not match the chain.
+    Corrupted,
+    /// The record with this sequence number is absent.
+    Missing,
+    /// The log ends inside a record.
+    Truncated,
+    /// A checkpoint is absent where the interval requires one, or its
+    /// digest differs from the published one.
+    Checkpoint,
+    /// The kernel step counter skips: an evaluation was never logged.
+    StepGap,
+}
+
+/// First point at which the log fails verification.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct AuditError {
+    /// Sequence number of the first bad or missing record.
+    pub seq: u64,
+    /// Byte offset in the log where it was expected.
+    pub offset: usize,
+    pub fault: Fault,
+}
+
+impl fmt::Display for AuditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let what = match self.fault {
+            Fault::Header => "bad header",
+            Fault::Corrupted => "corrupted",
+            Fault::Missing => "missing",
+            Fault::Truncated => "truncated",
+            Fault::Checkpoint => "checkpoint mismatch",
+            Fault::StepGap => "step counter gap",
+        };
+        write!(f, "record {} (offset {}): {what}", self.seq, self.offset)
+    }
+}
+
+impl std::error::Error for AuditError {}
+
+/// Summary of a log that verified.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct AuditSummary {
+    /// Evaluation records.
+    pub evaluations: u64,
+    /// Checkpoints found in the log.
+    pub checkpoints: Vec<Checkpoint>,
+    /// Hash of the last record.
+    pub head: [u8; SHA256_LEN],
+}
+
+/// Verify `log` against its own chain and against `published`
+/// checkpoint digests, reporting the first record that fails.
+///
+/// A published checkpoint past the end of the log means records were
+/// cut off: reported as `Missing` at the first absent sequence number.
+pub fn verify(log: &[u8], published: &[Checkpoint]) -> Result<AuditSummary, AuditError> {
+    let fail = |seq: u64, offset: usize, fault: Fault| Err(AuditError { seq, offset, fault });
+
+    if log.len() < HEADER_LEN
+        || &log[0..4] != MAGIC
+        || log[4..6] != VERSION.to_le_bytes()
+        || log[6..8] != [0, 0]
+    {
+        return fail(0, 0, Fault::Header);
+    }
+    let interval = u32::from_le_bytes([log[8], log[9], log[10], log[11]]);
+
+    let mut head = sha256(&[&log[..HEADER_LEN]]);
+    let mut summary = AuditSummary {
+        evaluations: 0,
+        checkpoints: Vec::new(),
+        head,
+    };
+    let mut since_checkpoint = 0u32;
+    let mut steps = 0u64;
+    let mut seq = 0u64;
+    let mut offset = HEADER_LEN;
+
+    while offset < log.len() {
+        let Some(record) = log.get(offset..offset + RECORD_LEN) else {
+            return fail(seq, offset, Fault::Truncated);
+        };
+        let recorded_seq = u64::from_le_bytes(record[1..9].try_into().unwrap());
+        let hash = sha256(&[&record[PREVIOUS], &record[..BODY_LEN]]);
+        if hash[..] != record[HASH] {
+            return fail(seq, offset, Fault::Corrupted);
+        }
+        if record[PREVIOUS] != head || recorded_seq != seq {
+            // An intact record further along the chain: the ones before
+            // it were removed.
+            let fault = if recorded_seq > seq {
+                Fault::Missing
+            } else {
+                Fault::Corrupted
+            };
+            return fail(seq, offset, fault);
+        }
+
+        let recorded_steps = u64::from_le_bytes(record[18..26].try_into().unwrap());
+        match record[0] {
+            KIND_EVALUATION if record[13] <= SafetyDecision::AtomicHalt as u8 => {
+                if interval != 0 && since_checkpoint == interval {
+                    return fail(seq, offset, Fault::Checkpoint);
+                }
+                // One step per evaluation; a halted kernel stays put.
+                let halted = record[13] == SafetyDecision::AtomicHalt as u8;
+                if recorded_steps != steps.saturating_add(1) && !(halted && recorded_steps == steps)
+                {
+                    return fail(seq, offset, Fault::StepGap);
+                }
+                steps = recorded_steps;
+                since_checkpoint += 1;
+                summary.evaluations += 1;
+            }
+            KIND_CHECKPOINT if record[9..14] == [0; 5] => {
+                if recorded_steps != steps {
+                    return fail(seq, offset, Fault::StepGap);
+                }
+                let checkpoint = Checkpoint { seq, digest: hash };
+                if published
+                    .iter()
+                    .any(|anchor| anchor.seq == seq && anchor.digest != hash)
+                {
+                    return fail(seq, offset, Fault::Checkpoint);
+                }
+                since_checkpoint = 0;
+                summary.checkpoints.push(checkpoint);
+            }
+            _ => return fail(seq, offset, Fault::Corrupted),
+        }
+
+        if published
+            .iter()
+            .any(|anchor| anchor.seq == seq && record[0] != KIND_CHECKPOINT)
+        {
+            return fail(seq, offset, Fault::Checkpoint);
+        }
+
+        head = hash;
+        seq += 1;
+        offset += RECORD_LEN;
+    }
+
+    if published.iter().any(|anchor| anchor.seq >= seq) {
+        return fail(seq, offset, Fault::Missing);
+    }
+    summary.head = head;
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Log of 10 evaluations with a checkpoint every 4; halts at 7.
+    fn logged() -> (Vec<u8>, Vec<Checkpoint>) {
+        let mut kernel = AuditedKernel::new(SafetyConfig::new(1.0), Vec::new(), 4).unwrap();
+        for step in 0..10 {
+            let deviation = if step == 7 { 2.0 } else { 0.1 * step as f32 };
+            kernel.evaluate(deviation).unwrap();
+        }
+        let last = kernel.checkpoint().unwrap();
+        let log = kernel.into_sink();
+
+        let summary = verify(&log, &[]).unwrap();
+        assert_eq!(summary.checkpoints.last(), Some(&last));
+        let published = summary.checkpoints.clone();
+        (log, published)
+    }
+
+    fn record_offset(seq: usize) -> usize {
+        HEADER_LEN + seq * RECORD_LEN
+    }
+
+    #[test]
+    fn intact_log_verifies() {
+        let (log, published) = logged();
+        let summary = verify(&log, &published).unwrap();
+
+        // 10 evaluations, automatic checkpoints at seq 4 and 9, manual at 12.
+        assert_eq!(summary.evaluations, 10);
+        let seqs: Vec<u64> = published.iter().map(|c| c.seq).collect();
+        assert_eq!(seqs, [4, 9, 12]);
+        assert_eq!(log.len(), record_offset(13));
+
+        // The halt and its reason are on record.
+        let halt = &log[record_offset(8)..record_offset(9)];
+        assert_eq!(halt[13], SafetyDecision::AtomicHalt as u8);
+        assert_eq!(u32::from_le_bytes(halt[14..18].try_into().unwrap()), 1);
+    }
+
+    #[test]
+    fn any_edited_byte_is_pinpointed() {
+        let (log, published) = logged();
+        for index in HEADER_LEN..log.len() {
+            let mut edited = log.clone();
+            edited[index] ^= 0x01;
+            let error = verify(&edited, &published).unwrap_err();
+            let seq = ((index - HEADER_LEN) / RECORD_LEN) as u64;
+            assert_eq!(error.seq, seq, "byte {index}");
+            assert_eq!(error.fault, Fault::Corrupted, "byte {index}");
+        }
+
+        let mut edited = log.clone();
+        edited[8] = 5;
+        assert_eq!(verify(&edited, &[]).unwrap_err().seq, 0);
+    }
+
+    #[test]
+    fn removed_records_are_reported_missing() {
+        let (log, published) = logged();
+
+        let mut cut = log.clone();
+        cut.drain(record_offset(2)..record_offset(3));
+        let error = verify(&cut, &published).unwrap_err();
+        assert_eq!((error.seq, error.fault), (2, Fault::Missing));
+        assert_eq!(error.offset, record_offset(2));
+
+        // Dropping the tail only shows against published checkpoints.
+        let tail = &log[..record_offset(11)];
+        assert!(verify(tail, &[]).is_ok());
+        let error = verify(tail, &published).unwrap_err();
+        assert_eq!((error.seq, error.fault), (11, Fault::Missing));
+
+        let error = verify(&log[..log.len() - 1], &published).unwrap_err();
+        assert_eq!((error.seq, error.fault), (12, Fault::Truncated));
+    }
+
+    #[test]
+    fn evaluation_left_out_of_the_chain_is_a_step_gap() {
+        let mut kernel = AuditedKernel::new(SafetyConfig::new(1.0), Vec::new(), 0).unwrap();
+        kernel.evaluate(0.1).unwrap();
+        // Evaluated behind the log's back: the chain stays intact.
+        let _ = kernel.kernel.evaluate(0.2);
+        kernel.evaluate(0.3).unwrap();
+        let log = kernel.into_sink();
+
+        let error = verify(&log, &[]).unwrap_err();
+        assert_eq!((error.seq, error.fault), (1, Fault::StepGap));
+    }
+
+    /// Accepts `budget` writes, then fails every write.
+    struct FailingSink {
+        budget: usize,
+        bytes: Vec<u8>,
+    }
+
+    impl Write for FailingSink {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.budget == 0 {
+                return Err(io::Error::other("disk full"));
+            }
+            self.budget -= 1;
+            self.bytes.write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn failed_write_poisons_the_kernel() {
+        let sink = FailingSink {
+            budget: 2,
+            bytes: Vec::new(),
+        };
+        let mut kernel = AuditedKernel::new(SafetyConfig::new(1.0), sink, 0).unwrap();
+        assert!(kernel.evaluate(0.1).is_ok());
+        assert!(kernel.evaluate(0.2).is_err());
+        assert!(kernel.is_poisoned());
+
+        kernel.sink.budget = usize::MAX;
+        assert!(kernel.evaluate(0.3).is_err());
+        assert!(kernel.checkpoint().is_err());
+        assert_eq!(kernel.kernel().steps(), 2);
+
+        let log = kernel.into_sink().bytes;
+        assert_eq!(verify(&log, &[]).unwrap().evaluations, 1);
+    }
+
+    #[test]
+    fn rewritten_log_fails_published_checkpoints() {
+        let (log, published) = logged();
+
+        // Re-run with a different history and a fully consistent chain.
+        let mut forged = AuditedKernel::new(SafetyConfig::new(1.0), Vec::new(), 4).unwrap();
+        for _ in 0..10 {
+            forged.evaluate(0.0).unwrap();
+        }
+        forged.checkpoint().unwrap();
+        let forged = forged.into_sink();
+        assert_eq!(forged.len(), log.len());
+        assert!(verify(&forged, &[]).is_ok());
+
+        let error = verify(&forged, &published).unwrap_err();
+        assert_eq!((error.seq, error.fault), (4, Fault::Checkpoint));
+    }
+}
@@ -0,0 +1,83 @@
+//! Verify a hash-chained audit log written by `AuditedKernel`.
+//!
+//! ```text
+//! fiolet-audit-verify [--checkpoint <seq>:<sha256-hex>]... <log>
+//! ```
+//!
+//! Each `--checkpoint` is a digest published when the log was written;
+//! the log must contain a checkpoint record with that sequence number
+//! and digest.
+//!
+//! Exit status: 0 log intact, 1 first bad or missing record printed,
+//! 2 usage error or unreadable log.
+
+use std::process::ExitCode;
+
+use fiolet_host::audit::{verify, Checkpoint};
+use fiolet_host::hash::{to_hex, SHA256_LEN};
+
+fn main() -> ExitCode {
+    let mut published = Vec::new();
+    let mut path = None;
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--checkpoint" {
+            match args.next().as_deref().and_then(parse_checkpoint) {
+                Some(checkpoint) => published.push(checkpoint),
+                None => return usage(),
+            }
+        } else if arg.starts_with("--") || path.is_some() {
+            return usage();
+        } else {
+            path = Some(arg);
+        }
+    }
+    let Some(path) = path else {
+        return usage();
+    };
+
+    let log = match std::fs::read(&path) {
+        Ok(log) => log,
+        Err(error) => {
+            eprintln!("{path}: {error}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match verify(&log, &published) {
+        Ok(summary) => {
+            println!(
+                "{path}: intact, {} evaluations, {} checkpoints, head {}",
+                summary.evaluations,
+                summary.checkpoints.len(),
+                to_hex(&summary.head)
+            );
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            println!("{path}: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn parse_checkpoint(arg: &str) -> Option<Checkpoint> {
+    let (seq, hex) = arg.split_once(':')?;
+    if hex.len() != 2 * SHA256_LEN {
+        return None;
+    }
+    let mut digest = [0u8; SHA256_LEN];
+    for (byte, pair) in digest.iter_mut().zip(hex.as_bytes().chunks_exact(2)) {
+        *byte = u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok()?;
+    }
+    Some(Checkpoint {
+        seq: seq.parse().ok()?,
+        digest,
+    })
+}
+
+fn usage() -> ExitCode {
+    eprintln!("usage: fiolet-audit-verify [--checkpoint <seq>:<sha256-hex>]... <log>");
+    ExitCode::from(2)
+}
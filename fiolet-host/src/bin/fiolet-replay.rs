@@ -0,0 +1,82 @@
+//! Replay recorded traces against this build's `SafetyKernel`.
+//!
+//! ```text
+//! fiolet-replay [--deviation-limit <f32>] <trace>...
+//! ```
+//!
+//! Each trace is re-run with its recorded configuration, or with the
+//! recorded configuration and an overridden deviation limit. Every step
+//! whose decision differs from the recording is printed.
+//!
+//! Exit status: 0 all decisions reproduced, 1 some decision differs,
+//! 2 usage error or unreadable trace. Suitable as a CI regression gate.
+
+use std::process::ExitCode;
+
+use fiolet_host::trace::{replay_with, Trace};
+
+fn main() -> ExitCode {
+    let mut limit = None;
+    let mut paths = Vec::new();
+
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--deviation-limit" {
+            match args.next().and_then(|value| value.parse::<f32>().ok()) {
+                Some(value) => limit = Some(value),
+                None => return usage(),
+            }
+        } else if arg.starts_with("--") {
+            return usage();
+        } else {
+            paths.push(arg);
+        }
+    }
+    if paths.is_empty() {
+        return usage();
+    }
+
+    let mut diverged = false;
+    for path in &paths {
+        let trace = match std::fs::read(path)
+            .map_err(|error| error.to_string())
+            .and_then(|bytes| Trace::decode(&bytes).map_err(|error| error.to_string()))
+        {
+            Ok(trace) => trace,
+            Err(error) => {
+                eprintln!("{path}: {error}");
+                return ExitCode::from(2);
+            }
+        };
+
+        let mut config = trace.config;
+        if let Some(limit) = limit {
+            config.deviation_limit = limit;
+        }
+
+        let divergences = replay_with(&trace, config);
+        for divergence in &divergences {
+            println!(
+                "{path}: step {}: deviation {:?}: recorded {:?}, replayed {:?}",
+                divergence.index, divergence.deviation, divergence.recorded, divergence.replayed
+            );
+        }
+        println!(
+            "{path}: {} steps, {} differing",
+            trace.steps.len(),
+            divergences.len()
+        );
+        diverged |= !divergences.is_empty();
+    }
+
+    if diverged {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn usage() -> ExitCode {
+    eprintln!("usage: fiolet-replay [--deviation-limit <f32>] <trace>...");
+    ExitCode::from(2)
+}
@@ -0,0 +1,40 @@
+//! Export a recorded trace as a TLA+ module and TLC configuration.
+//!
+//! ```text
+//! fiolet-tla-export <trace> <out-dir> <ModuleName>
+//! ```
+//!
+//! Writes `<out-dir>/<ModuleName>.tla` and `<out-dir>/<ModuleName>.cfg`.
+//! Exit status: 0 written, 2 usage error, unreadable trace or export
+//! failure.
+
+use std::path::Path;
+use std::process::ExitCode;
+
+use fiolet_host::tla::export;
+use fiolet_host::trace::Trace;
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let [trace, dir, name] = args.as_slice() else {
+        eprintln!("usage: fiolet-tla-export <trace> <out-dir> <ModuleName>");
+        return ExitCode::from(2);
+    };
+
+    let result = std::fs::read(trace)
+        .map_err(|error| error.to_string())
+        .and_then(|bytes| Trace::decode(&bytes).map_err(|error| error.to_string()))
+        .and_then(|trace| export(&trace, name).map_err(|error| error.to_string()))
+        .and_then(|tla| {
+            tla.write_to(Path::new(dir))
+                .map_err(|error| error.to_string())
+        });
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("{trace}: {error}");
+            ExitCode::from(2)
+        }
+    }
+}
@@ -0,0 +1,340 @@
+//! Durable halt latch across process restarts.
+//!
+//! A halted kernel's latch lives in memory; a crash or restart would
+//! otherwise bring the session back up running. `HaltJournal` records
+//! each session's halt in a checksummed file BEFORE the halt is reported
+//! to the caller, and recreates kernels for that session already halted.
+//!
+//! Fail-closed: a journal that is present but unreadable, truncated,
+//! from an unknown version, written for another session, or failing its
+//! checksum restores the kernel halted with `StateCorrupted`. Only a
+//! session with no journal file at all starts running.
+//!
+//! Record layout (little-endian, `RECORD_LEN` bytes):
+//!
+//! ```text
+//! 0   magic      b"FHLJ"
+//! 4   version    u16 (1)
+//! 6   reserved   u16 (0)
+//! 8   session    u64  FNV-1a of the session ID
+//! 16  reason     u32  HaltReason code
+//! 20  value      u32  f32 bits
+//! 24  step       u64
+//! 32  channel    u32  (u32::MAX: none)
+//! 36  steps      u64  kernel step counter at latch
+//! 44  checksum   u64  FNV-1a of bytes 0..44
+//! ```
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use fiolet_core::{HaltCause, HaltReason, SafetyConfig, SafetyKernel, Verdict};
+
+use crate::hash::fnv1a64;
+
+const MAGIC: &[u8; 4] = b"FHLJ";
+const VERSION: u16 = 1;
+
+/// Size of one journal record in bytes.
+pub const RECORD_LEN: usize = 52;
+
+/// Longest accepted session ID.
+pub const MAX_SESSION_LEN: usize = 128;
+
+/// What the journal says about a session.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum LatchState {
+    /// No journal file: the session never halted.
+    Clear,
+    /// The session halted with this cause after `steps` evaluations.
+    Halted { cause: HaltCause, steps: u64 },
+    /// A journal exists but cannot be trusted.
+    Corrupt,
+}
+
+/// Directory of per-session halt journals.
+#[derive(Clone, Debug)]
+pub struct HaltJournal {
+    dir: PathBuf,
+}
+
+impl HaltJournal {
+    /// Use (and create if needed) `dir` for journal files.
+    pub fn open(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    /// Read the latch state of `session`.
+    ///
+    /// An invalid session ID reads as `Corrupt`.
+    pub fn state(&self, session: &str) -> LatchState {
+        let Some(path) = self.path(session) else {
+            return LatchState::Corrupt;
+        };
+        match fs::read(path) {
+            Ok(bytes) => decode(session, &bytes),
+            Err(error) if error.kind() == io::ErrorKind::NotFound => LatchState::Clear,
+            Err(_) => LatchState::Corrupt,
+        }
+    }
+
+    /// Durably record that `session` halted.
+    ///
+    /// Written to a temporary file, synced, renamed into place and the
+    /// directory synced, so a crash leaves either no record or a whole one.
+    /// An existing record is kept: the first cause wins.
+    pub fn record(&self, session: &str, cause: &HaltCause, steps: u64) -> io::Result<()> {
+        let path = self
+            .path(session)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "invalid session ID"))?;
+        if let LatchState::Halted { .. } = self.state(session) {
+            return Ok(());
+        }
+
+        let tmp = path.with_extension("halt.tmp");
+        let mut file = File::create(&tmp)?;
+        file.write_all(&encode(session, cause, steps))?;
+        file.sync_all()?;
+        drop(file);
+
+        fs::rename(&tmp, &path)?;
+        sync_dir(&self.dir)
+    }
+
+    /// Kernel for `session`: running only if the journal is clear.
+    pub fn restore(&self, session: &str, config: SafetyConfig) -> SafetyKernel {
+        match self.state(session) {
+            LatchState::Clear => SafetyKernel::new(config),
+            LatchState::Halted { cause, steps } => SafetyKernel::new_halted(config, cause, steps),
+            LatchState::Corrupt => SafetyKernel::new_halted(
+                config,
+                HaltCause::restore(HaltReason::StateCorrupted, f32::NAN, 0, None),
+                0,
+            ),
+        }
+    }
+
+    /// Journal file of `session`, if the ID is acceptable as a file name.
+    fn path(&self, session: &str) -> Option<PathBuf> {
+        let valid = !session.is_empty()
+            && session.len() <= MAX_SESSION_LEN
+            && !session.starts_with('.')
+            && session
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"-_.".contains(&byte));
+        valid.then(|| self.dir.join(format!("{session}.halt")))
+    }
+}
+
+/// Kernel whose halt is journaled before it is reported.
+pub struct DurableKernel {
+    kernel: SafetyKernel,
+    journal: HaltJournal,
+    session: String,
+    /// A restart would restore the session halted.
+    persisted: bool,
+}
+
+impl DurableKernel {
+    /// Restore `session` from `journal` (halted if it ever halted).
+    pub fn restore(journal: HaltJournal, session: &str, config: SafetyConfig) -> Self {
+        Self {
+            kernel: journal.restore(session, config),
+            persisted: journal.state(session) != LatchState::Clear,
+            journal,
+            session: session.to_owned(),
+        }
+    }
+
+    /// Evaluate a deviation.
+    ///
+    /// A halt is returned only once it is on disk. `Err` means the
+    /// kernel is halted but the journal write failed; treat it as a halt.
+    /// The write is retried on every later call until it succeeds.
+    pub fn evaluate(&mut self, deviation: f32) -> io::Result<Verdict> {
+        let verdict = self.kernel.evaluate(deviation);
+
+        if self.kernel.is_halted() && !self.persisted {
+            if let Some(cause) = self.kernel.halt_cause() {
+                self.journal
+                    .record(&self.session, &cause, self.kernel.steps())?;
+                self.persisted = true;
+            }
+        }
+        Ok(verdict)
+    }
+
+    /// The wrapped kernel.
+    pub fn kernel(&self) -> &SafetyKernel {
+        &self.kernel
+    }
+}
+
+fn encode(session: &str, cause: &HaltCause, steps: u64) -> [u8; RECORD_LEN] {
+    let mut record = [0u8; RECORD_LEN];
+    record[0..4].copy_from_slice(MAGIC);
+    record[4..6].copy_from_slice(&VERSION.to_le_bytes());
+    record[8..16].copy_from_slice(&fnv1a64(session.as_bytes()).to_le_bytes());
+    record[16..20].copy_from_slice(&(cause.reason() as u32).to_le_bytes());
+    record[20..24].copy_from_slice(&cause.value().to_bits().to_le_bytes());
+    record[24..32].copy_from_slice(&cause.step().to_le_bytes());
+    let channel = cause.channel().unwrap_or(HaltCause::NO_CHANNEL);
+    record[32..36].copy_from_slice(&channel.to_le_bytes());
+    record[36..44].copy_from_slice(&steps.to_le_bytes());
+    let checksum = fnv1a64(&record[..44]);
+    record[44..52].copy_from_slice(&checksum.to_le_bytes());
+    record
+}
+
+fn decode(session: &str, bytes: &[u8]) -> LatchState {
+    let Ok(record) = <&[u8; RECORD_LEN]>::try_from(bytes) else {
+        return LatchState::Corrupt;
+    };
+    let u16_at = |at: usize| u16::from_le_bytes([record[at], record[at + 1]]);
+    let u32_at = |at: usize| u32::from_le_bytes(record[at..at + 4].try_into().unwrap());
+    let u64_at = |at: usize| u64::from_le_bytes(record[at..at + 8].try_into().unwrap());
+
+    if &record[0..4] != MAGIC
+        || u16_at(4) != VERSION
+        || u16_at(6) != 0
+        || u64_at(8) != fnv1a64(session.as_bytes())
+        || u64_at(44) != fnv1a64(&record[..44])
+    {
+        return LatchState::Corrupt;
+    }
+    let Some(reason) = HaltReason::from_code(u32_at(16)) else {
+        return LatchState::Corrupt;
+    };
+
+    let channel = u32_at(32);
+    let cause = HaltCause::restore(
+        reason,
+        f32::from_bits(u32_at(20)),
+        u64_at(24),
+        (channel != HaltCause::NO_CHANNEL).then_some(channel),
+    );
+    LatchState::Halted {
+        cause,
+        steps: u64_at(36),
+    }
+}
+
+#[cfg(unix)]
+fn sync_dir(dir: &Path) -> io::Result<()> {
+    File::open(dir)?.sync_all()
+}
+
+#[cfg(not(unix))]
+fn sync_dir(_dir: &Path) -> io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fiolet_core::SafetyDecision;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    /// Fresh, empty directory under the system temp dir.
+    fn scratch_dir(name: &str) -> PathBuf {
+        static COUNTER: AtomicU32 = AtomicU32::new(0);
+        let dir = std::env::temp_dir().join(format!(
+            "fiolet-{name}-{}-{}",
+            std::process::id(),
+            COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = fs::remove_dir_all(&dir);
+        dir
+    }
+
+    #[test]
+    fn halt_survives_restart() {
+        let dir = scratch_dir("journal");
+        let config = SafetyConfig::new(1.0);
+
+        let mut kernel = DurableKernel::restore(HaltJournal::open(&dir).unwrap(), "s-1", config);
+        assert_eq!(kernel.evaluate(0.5).unwrap(), SafetyDecision::Continue);
+        assert_eq!(kernel.evaluate(3.0).unwrap(), SafetyDecision::AtomicHalt);
+        drop(kernel);
+
+        // "Restart": a new process opens the same journal.
+        let journal = HaltJournal::open(&dir).unwrap();
+        let mut restored = DurableKernel::restore(journal.clone(), "s-1", config);
+        assert!(restored.kernel().is_halted());
+        assert_eq!(restored.evaluate(0.0).unwrap(), SafetyDecision::AtomicHalt);
+
+        let cause = restored.kernel().halt_cause().unwrap();
+        assert_eq!(cause.reason(), HaltReason::ThresholdExceeded);
+        assert_eq!(cause.value(), 3.0);
+        assert_eq!(cause.step(), 1);
+        assert_eq!(restored.kernel().steps(), 2);
+
+        // Other sessions are unaffected.
+        assert_eq!(journal.state("s-2"), LatchState::Clear);
+        assert!(!journal.restore("s-2", config).is_halted());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn failed_journal_write_is_retried_until_persisted() {
+        let dir = scratch_dir("journal-retry");
+        let journal = HaltJournal::open(&dir).unwrap();
+        let mut kernel = DurableKernel::restore(journal.clone(), "s", SafetyConfig::new(1.0));
+        assert_eq!(kernel.evaluate(0.5).unwrap(), SafetyDecision::Continue);
+
+        // Remove the directory: unwritable even when running as root.
+        fs::remove_dir_all(&dir).unwrap();
+        assert!(kernel.evaluate(3.0).is_err());
+        assert!(kernel.evaluate(0.0).is_err());
+        assert!(kernel.kernel().is_halted());
+
+        fs::create_dir_all(&dir).unwrap();
+        assert_eq!(kernel.evaluate(0.0).unwrap(), SafetyDecision::AtomicHalt);
+        let LatchState::Halted { cause, steps } = journal.state("s") else {
+            panic!("halt not persisted");
+        };
+        assert_eq!(cause.reason(), HaltReason::ThresholdExceeded);
+        assert_eq!(cause.value(), 3.0);
+        assert_eq!(steps, 2);
+        assert_eq!(kernel.evaluate(0.0).unwrap(), SafetyDecision::AtomicHalt);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn corrupt_or_foreign_journal_starts_halted() {
+        let dir = scratch_dir("journal-corrupt");
+        let journal = HaltJournal::open(&dir).unwrap();
+        let cause = HaltCause::restore(HaltReason::NonFinite, f32::NAN, 4, None);
+        journal.record("a", &cause, 5).unwrap();
+
+        let path = dir.join("a.halt");
+        let good = fs::read(&path).unwrap();
+        for index in 0..RECORD_LEN {
+            let mut bad = good.clone();
+            bad[index] ^= 0x10;
+            fs::write(&path, &bad).unwrap();
+            assert_eq!(journal.state("a"), LatchState::Corrupt, "byte {index}");
+        }
+        fs::write(&path, &good[..RECORD_LEN - 1]).unwrap();
+        assert_eq!(journal.state("a"), LatchState::Corrupt);
+
+        // A record copied to another session's file is rejected.
+        fs::write(dir.join("b.halt"), &good).unwrap();
+        assert_eq!(journal.state("b"), LatchState::Corrupt);
+        let restored = journal.restore("b", SafetyConfig::new(1.0));
+        assert_eq!(
+            restored.halt_cause().unwrap().reason(),
+            HaltReason::StateCorrupted
+        );
+
+        assert_eq!(journal.state("../escape"), LatchState::Corrupt);
+        assert!(journal.restore("", SafetyConfig::new(1.0)).is_halted());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -5,11 +5,53 @@
 //!
 //! It is NOT part of the safety boundary.
 
-use fiolet_core::{SafetyConfig, SafetyDecision, SafetyKernel};
+pub mod audit;
+pub mod hash;
+pub mod journal;
+pub mod spec;
+pub mod tla;
+pub mod trace;
+
+use fiolet_core::{GateError, OutputGate, SafetyConfig, SafetyKernel};
+
+/// One guarded generation stream: kernel decision → output gate.
+///
+/// Each step's bytes are buffered in the gate and released only if the
+/// kernel returns `Continue` for that step's deviation. The first halt
+/// wipes the pending bytes; the stream is silent from then on.
+pub struct GatedStream<const N: usize> {
+    kernel: SafetyKernel,
+    gate: OutputGate<N>,
+}
+
+impl<const N: usize> GatedStream<N> {
+    pub fn new(config: SafetyConfig) -> Self {
+        Self {
+            kernel: SafetyKernel::new(config),
+            gate: OutputGate::new(),
+        }
+    }
+
+    /// Buffer `bytes` for the current step.
+    pub fn write(&mut self, bytes: &[u8]) -> Result<(), GateError> {
+        self.gate.write(bytes)
+    }
+
+    /// Evaluate the step's deviation and release its bytes on `Continue`.
+    pub fn end_step(&mut self, deviation: f32) -> Result<&[u8], GateError> {
+        let verdict = self.kernel.evaluate(deviation);
+        self.gate.close_step(verdict)
+    }
+
+    pub fn is_silenced(&self) -> bool {
+        self.gate.is_sealed()
+    }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use fiolet_core::SafetyDecision;
 
     #[test]
     fn continue_below_limit() {
@@ -61,6 +103,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn gated_stream_is_silent_after_halt() {
+        let mut stream = GatedStream::<64>::new(SafetyConfig::new(1.0));
+        let mut shown = Vec::new();
+
+        let steps: [(&[u8], f32); 4] = [
+            (b"The ", 0.1),
+            (b"answer ", 0.2),
+            (b"is: ", 3.0),
+            (b"42", 0.0),
+        ];
+        for (bytes, deviation) in steps {
+            if stream.write(bytes).is_ok() {
+                if let Ok(released) = stream.end_step(deviation) {
+                    shown.extend_from_slice(released);
+                }
+            }
+        }
+
+        assert_eq!(shown, b"The answer ");
+        assert!(stream.is_silenced());
+        assert_eq!(stream.write(b"!"), Err(GateError::Sealed));
+    }
+
     #[test]
     fn deterministic_behavior() {
         let mut k1 = SafetyKernel::new(SafetyConfig::new(1.0));
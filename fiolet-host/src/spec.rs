@@ -0,0 +1,306 @@
+//! Reference interpreter of `SafetyKernel.tla`.
+//!
+//! A literal transcription of the spec's `Init` and `Next`, kept apart
+//! from `fiolet_core` so the two can be run side by side: the
+//! differential suite in this module's tests drives both with the same
+//! inputs and requires the same decision and halted state at every step.
+//!
+//! Mapping onto the spec:
+//!
+//! - `deviation` and `limit` are TLA+ values. An `f32` is `Real` when
+//!   finite (held exactly as `f64`, so comparisons are real-number
+//!   comparisons, not `f32` ones); NaN and ±∞ are values outside `Real`.
+//! - `Init` leaves `deviation` unconstrained and `Next` keeps it
+//!   unchanged: the environment chooses it. `next` therefore first sets
+//!   `deviation` to the evaluated input, then applies `Next`.
+//! - The decision of a step is `ATOMIC_HALT` iff `halted'` holds, as in
+//!   `FioletSafetyKernel.tla` and the `SafetyKernel.cfg` symbols.
+//!
+//! The spec covers only the hard limit. Step budgets, windows, slopes,
+//! baselines, lower limits and configurations the kernel rejects
+//! (`limit` negative or not finite) are outside it and are not modelled.
+
+use fiolet_core::SafetyDecision;
+
+/// A TLA+ value of `deviation` or `limit`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub enum SpecValue {
+    /// A member of `Real`.
+    Real(f64),
+    /// NaN or ±∞: `\notin Real`.
+    NotReal,
+}
+
+impl SpecValue {
+    /// Abstraction of an `f32`; exact for every finite value.
+    pub fn from_f32(value: f32) -> Self {
+        if value.is_finite() {
+            SpecValue::Real(f64::from(value))
+        } else {
+            SpecValue::NotReal
+        }
+    }
+}
+
+/// One state of `SafetyKernel.tla`.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct SpecState {
+    pub halted: bool,
+    /// `None` until the environment first chooses it.
+    pub deviation: Option<SpecValue>,
+    pub limit: SpecValue,
+}
+
+impl SpecState {
+    /// `Init`: `halted = FALSE /\ limit = DeviationLimit`.
+    pub fn init(deviation_limit: f32) -> Self {
+        Self {
+            halted: false,
+            deviation: None,
+            limit: SpecValue::from_f32(deviation_limit),
+        }
+    }
+
+    /// `UnsafeDeviation`: `deviation \notin Real \/ deviation > limit`.
+    pub fn unsafe_deviation(&self) -> bool {
+        match (self.deviation, self.limit) {
+            (Some(SpecValue::Real(deviation)), SpecValue::Real(limit)) => deviation > limit,
+            // Not a real, or compared against a non-real limit.
+            _ => true,
+        }
+    }
+
+    /// Environment sets `deviation`, then `Next`.
+    ///
+    /// Returns the successor state and the step's decision.
+    pub fn next(&self, deviation: f32) -> (Self, SafetyDecision) {
+        let current = Self {
+            deviation: Some(SpecValue::from_f32(deviation)),
+            ..*self
+        };
+
+        // IF halted THEN halted' = TRUE
+        // ELSE IF UnsafeDeviation THEN halted' = TRUE ELSE halted' = FALSE
+        let halted = if current.halted {
+            true
+        } else {
+            current.unsafe_deviation()
+        };
+        // UNCHANGED << deviation, limit >>
+        let successor = Self { halted, ..current };
+
+        let decision = if successor.halted {
+            SafetyDecision::AtomicHalt
+        } else {
+            SafetyDecision::Continue
+        };
+        (successor, decision)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fiolet_core::{SafetyConfig, SafetyKernel};
+
+    #[test]
+    fn next_follows_the_spec_cases() {
+        let state = SpecState::init(1.0);
+        assert!(!state.halted);
+
+        let (state, decision) = state.next(1.0);
+        assert_eq!(decision, SafetyDecision::Continue);
+        assert_eq!(state.deviation, Some(SpecValue::Real(1.0)));
+
+        let (state, decision) = state.next(f32::NEG_INFINITY);
+        assert_eq!(decision, SafetyDecision::AtomicHalt);
+        assert!(state.halted);
+
+        let (state, decision) = state.next(0.0);
+        assert_eq!(decision, SafetyDecision::AtomicHalt);
+        assert_eq!(state.limit, SpecValue::Real(1.0));
+    }
+
+    // Differential suite: `fiolet_core::SafetyKernel` against this
+    // interpreter. Random deviation sequences, weighted toward the values
+    // where float semantics and the spec's reals can part ways: NaN
+    // payloads, ±∞, ±0, subnormals, the limit itself and its neighbours.
+    // Every step must agree on decision and halted state. A failure
+    // prints the seed; rerun it with `FIOLET_SPEC_SEED=<seed>`. The
+    // generator is a hand-written SplitMix64: no external crates, every
+    // case reproducible.
+
+    const CASES: u64 = 10_000;
+    const MAX_LEN: u64 = 64;
+
+    /// SplitMix64 (Steele, Lea, Flood 2014).
+    struct SplitMix64(u64);
+
+    impl SplitMix64 {
+        fn next(&mut self) -> u64 {
+            self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+            let mut z = self.0;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+            z ^ (z >> 31)
+        }
+
+        fn below(&mut self, bound: u64) -> u64 {
+            self.next() % bound
+        }
+
+        fn sign(&mut self) -> u32 {
+            (self.next() as u32) & 0x8000_0000
+        }
+
+        /// Uniform in `[0, 1)`.
+        fn unit(&mut self) -> f32 {
+            (self.next() >> 40) as f32 / (1u64 << 24) as f32
+        }
+    }
+
+    /// Adjacent float toward +∞.
+    fn next_up(value: f32) -> f32 {
+        let bits = value.to_bits();
+        if value.is_nan() || value == f32::INFINITY {
+            value
+        } else if value == 0.0 {
+            f32::from_bits(1)
+        } else if bits & 0x8000_0000 == 0 {
+            f32::from_bits(bits + 1)
+        } else {
+            f32::from_bits(bits - 1)
+        }
+    }
+
+    fn next_down(value: f32) -> f32 {
+        -next_up(-value)
+    }
+
+    /// A valid limit: finite and non-negative, often at an edge.
+    fn limit(rng: &mut SplitMix64) -> f32 {
+        match rng.below(7) {
+            0 => 0.0,
+            1 => f32::from_bits(1 + rng.below(0x7f_ffff) as u32),
+            2 => f32::MIN_POSITIVE,
+            3 => 1.0,
+            4 => f32::MAX,
+            5 => 100.0 * rng.unit(),
+            _ => f32::from_bits(rng.below(0x7f80_0000) as u32),
+        }
+    }
+
+    fn deviation(rng: &mut SplitMix64, limit: f32) -> f32 {
+        match rng.below(24) {
+            0 => f32::from_bits(rng.sign() | 0x7fc0_0000 | rng.below(0x40_0000) as u32),
+            1 => f32::from_bits(rng.sign() | 0x7f80_0000 | (1 + rng.below(0x3f_ffff) as u32)),
+            2 => f32::INFINITY,
+            3 => f32::NEG_INFINITY,
+            4 => f32::from_bits(rng.sign()),
+            5 => f32::from_bits(rng.sign() | (1 + rng.below(0x7f_ffff) as u32)),
+            6 => f32::from_bits(rng.sign() | f32::MIN_POSITIVE.to_bits()),
+            7 => f32::from_bits(rng.sign() | f32::MAX.to_bits()),
+            8 => limit,
+            9 => next_up(limit),
+            10 => next_down(limit),
+            11 => f32::from_bits(rng.next() as u32),
+            // Below the limit, so sequences run long enough to matter.
+            _ => limit * rng.unit() - rng.unit(),
+        }
+    }
+
+    /// Run one generated case; `Err` describes the first disagreement.
+    fn run_case(seed: u64) -> Result<(), String> {
+        let mut rng = SplitMix64(seed);
+        let limit = limit(&mut rng);
+        let len = rng.below(MAX_LEN + 1);
+
+        let mut kernel = SafetyKernel::new(SafetyConfig::new(limit));
+        let mut model = SpecState::init(limit);
+        if kernel.is_halted() != model.halted {
+            return Err(format!("limit {limit:?}: initial halted state differs"));
+        }
+
+        for step in 0..len {
+            let input = deviation(&mut rng, limit);
+            let decision = kernel.evaluate(input).decision();
+            let (successor, expected) = model.next(input);
+
+            if decision != expected || kernel.is_halted() != successor.halted {
+                return Err(format!(
+                    "limit {limit:?}, step {step}, deviation {input:?} ({:#010x}): \
+                     kernel {decision:?}/halted={}, spec {expected:?}/halted={}",
+                    input.to_bits(),
+                    kernel.is_halted(),
+                    successor.halted
+                ));
+            }
+            model = successor;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn kernel_agrees_with_spec_on_random_sequences() {
+        if let Ok(seed) = std::env::var("FIOLET_SPEC_SEED") {
+            let seed = seed.parse().expect("FIOLET_SPEC_SEED must be a u64");
+            if let Err(failure) = run_case(seed) {
+                panic!("seed {seed}: {failure}");
+            }
+            return;
+        }
+
+        for seed in 0..CASES {
+            if let Err(failure) = run_case(seed) {
+                panic!("seed {seed}: {failure}");
+            }
+        }
+    }
+
+    #[test]
+    fn kernel_agrees_with_spec_on_edge_values() {
+        let limits = [0.0, f32::from_bits(1), f32::MIN_POSITIVE, 1.0, f32::MAX];
+        for limit in limits {
+            let edges = [
+                f32::NAN,
+                -f32::NAN,
+                f32::INFINITY,
+                f32::NEG_INFINITY,
+                0.0,
+                -0.0,
+                f32::from_bits(1),
+                -f32::from_bits(1),
+                f32::MIN_POSITIVE,
+                f32::MAX,
+                f32::MIN,
+                limit,
+                next_up(limit),
+                next_down(limit),
+            ];
+            for input in edges {
+                let mut kernel = SafetyKernel::new(SafetyConfig::new(limit));
+                let (model, expected) = SpecState::init(limit).next(input);
+                assert_eq!(
+                    kernel.evaluate(input).decision(),
+                    expected,
+                    "limit {limit:?}, deviation {input:?}"
+                );
+                assert_eq!(kernel.is_halted(), model.halted);
+            }
+        }
+    }
+
+    #[test]
+    fn generator_reaches_every_class() {
+        let mut rng = SplitMix64(7);
+        let (mut nan, mut inf, mut subnormal, mut continues) = (0, 0, 0, 0);
+        for _ in 0..10_000 {
+            let value = deviation(&mut rng, 1.0);
+            nan += value.is_nan() as u32;
+            inf += value.is_infinite() as u32;
+            subnormal += value.is_subnormal() as u32;
+            continues += (value.is_finite() && value <= 1.0) as u32;
+        }
+        assert!(nan > 0 && inf > 0 && subnormal > 0 && continues > 4_000);
+    }
+}
@@ -0,0 +1,473 @@
+//! Export recorded executions as TLA+ traces for TLC.
+//!
+//! `export` turns a `Trace` (see `trace`) into a module and a TLC
+//! configuration that replay it state by state and check every step
+//! against `SafetyKernel.tla` and, when it applies, against
+//! `formal_specs/FioletSafetyKernel.tla`. A production execution that the
+//! specs do not allow is reported by TLC as a property violation at the
+//! offending state.
+//!
+//! Behaviour shape (variables `halted`, `deviation`, `limit`, `decision`
+//! plus `phase`):
+//!
+//! - one boot state: the fresh kernel, `halted = FALSE`,
+//!   `decision = "CONTINUE"`, `deviation` = 0 (the specs leave it free);
+//! - per evaluation, an environment step choosing `deviation` (the specs
+//!   treat it as an external signal; `SafetyKernel.tla`'s `Next` keeps
+//!   it UNCHANGED), then a kernel step checked against the spec's `Next`.
+//!
+//! Finite-float abstraction: TLC has no real numbers, so each finite
+//! `f32` is written as its order key (`float_key`), an integer that
+//! preserves `<`, `=` (−0 = +0) and negation exactly. NaN and ±∞ are
+//! written as strings outside the key range, and the configuration
+//! overrides `Real` with that range, so `deviation \notin Real` holds for
+//! exactly the non-finite inputs. No other arithmetic is preserved:
+//! `SafetyKernel.tla` needs only `>`, and `FioletSafetyKernel`'s
+//! `Deviation` with `baseline` 0 needs only negation.
+//!
+//! The `FioletSafetyKernel.tla` mapping is a two-sided band: with
+//! `baseline` 0 it requires a halt whenever `|deviation| > limit`, while
+//! the absolute-mode kernel only bounds `deviation > limit`. The spec also
+//! admits only real metrics. Its properties are therefore left out of
+//! the configuration, with a comment saying why, for a trace with a
+//! non-finite deviation or a deviation below `-limit`; on such traces a
+//! correct kernel would be reported as violating them.
+//!
+//! Baseline-relative configurations are not exported: the order keys
+//! cannot express `metric − baseline`, and `SafetyKernel.tla` has no
+//! lower limit. Neither are configurations with a rule the specs' `Next`
+//! does not model (step budget, window, slope limit) or with a negative
+//! limit, which halts at construction: a correct kernel halting on such
+//! a rule would be reported as violating the specs.
+
+use core::fmt::{self, Write as _};
+use std::io;
+use std::path::Path;
+
+use fiolet_core::{SafetyConfig, SafetyDecision};
+
+use crate::trace::Trace;
+
+/// Largest finite order key (`f32::MAX`).
+pub const MAX_FLOAT_KEY: i32 = 0x7f7f_ffff;
+
+/// Order key of a finite `f32`: the bit pattern read as sign-magnitude.
+///
+/// `x < y ⇔ key(x) < key(y)`, `x == y ⇔ key(x) == key(y)` and
+/// `key(−x) == −key(x)` for all finite `x`, `y`. `None` for NaN and ±∞.
+pub fn float_key(value: f32) -> Option<i32> {
+    if !value.is_finite() {
+        return None;
+    }
+    let magnitude = (value.to_bits() & 0x7fff_ffff) as i32;
+    Some(if value.is_sign_negative() {
+        -magnitude
+    } else {
+        magnitude
+    })
+}
+
+/// Why a trace cannot be exported.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum ExportError {
+    /// The module name is not a TLA+ identifier.
+    InvalidName,
+    /// `deviation_limit` is NaN or infinite; the specs need a real limit.
+    NonFiniteLimit,
+    /// The configuration sets a baseline or a lower limit.
+    BaselineBand,
+    /// The configuration sets a step budget, window or slope limit, or a
+    /// negative `deviation_limit`; the specs model only the hard limit.
+    UnmodelledRule,
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::InvalidName => write!(f, "module name is not a TLA+ identifier"),
+            ExportError::NonFiniteLimit => write!(f, "deviation limit is not finite"),
+            ExportError::BaselineBand => {
+                write!(f, "baseline-relative configurations are not expressible")
+            }
+            ExportError::UnmodelledRule => {
+                write!(f, "configuration uses a rule the specs do not model")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+/// A TLA+ trace module and its TLC configuration.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct TlaTrace {
+    pub name: String,
+    /// Contents of `<name>.tla`.
+    pub module: String,
+    /// Contents of `<name>.cfg`.
+    pub config: String,
+}
+
+impl TlaTrace {
+    /// Write `<name>.tla` and `<name>.cfg` into `dir`.
+    pub fn write_to(&self, dir: &Path) -> io::Result<()> {
+        std::fs::write(dir.join(format!("{}.tla", self.name)), &self.module)?;
+        std::fs::write(dir.join(format!("{}.cfg", self.name)), &self.config)
+    }
+}
+
+/// Export `trace` as module `name`.
+pub fn export(trace: &Trace, name: &str) -> Result<TlaTrace, ExportError> {
+    let valid_name = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || byte == b'_');
+    if !valid_name {
+        return Err(ExportError::InvalidName);
+    }
+    if !trace.config.is_absolute() {
+        return Err(ExportError::BaselineBand);
+    }
+    let limit = float_key(trace.config.deviation_limit).ok_or(ExportError::NonFiniteLimit)?;
+    if trace.config.max_steps != SafetyConfig::NO_STEP_BUDGET
+        || trace.config.window.is_enabled()
+        || trace.config.slope_limit.is_finite()
+        || trace.config.deviation_limit < 0.0
+    {
+        return Err(ExportError::UnmodelledRule);
+    }
+    let fiolet_kernel = if !trace.steps.iter().all(|step| step.deviation.is_finite()) {
+        FioletKernelCheck::NonFinite
+    } else if trace
+        .steps
+        .iter()
+        .any(|step| step.deviation < -trace.config.deviation_limit)
+    {
+        FioletKernelCheck::BelowLowerBand
+    } else {
+        FioletKernelCheck::Applies
+    };
+
+    Ok(TlaTrace {
+        name: name.to_owned(),
+        module: module(trace, name, limit),
+        config: config(fiolet_kernel),
+    })
+}
+
+/// Whether the `FioletSafetyKernel.tla` properties hold for a correct
+/// kernel on this trace.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum FioletKernelCheck {
+    Applies,
+    /// A deviation is NaN or infinite: `metric \notin Real`.
+    NonFinite,
+    /// A deviation is below `-limit`: the spec halts, the kernel need not.
+    BelowLowerBand,
+}
+
+fn value(deviation: f32) -> String {
+    match float_key(deviation) {
+        Some(key) => key.to_string(),
+        None if deviation.is_nan() => "NaN".to_owned(),
+        None if deviation > 0.0 => "PosInf".to_owned(),
+        None => "NegInf".to_owned(),
+    }
+}
+
+fn decision(decision: SafetyDecision) -> &'static str {
+    match decision {
+        SafetyDecision::Continue => "\"CONTINUE\"",
+        SafetyDecision::AtomicHalt => "\"ATOMIC_HALT\"",
+    }
+}
+
+fn module(trace: &Trace, name: &str, limit: i32) -> String {
+    let rule = "-".repeat(28);
+    let mut out = String::new();
+
+    // Writing to a String cannot fail.
+    let _ = writeln!(out, "{rule} MODULE {name} {rule}");
+    let _ = write!(
+        out,
+        r#"\* Recorded SafetyKernel execution exported by fiolet-host.
+\* {steps} evaluations, deviation_limit = {limit_value:?} ({limit_bits:#010x}).
+\* Check: tlc -config {name}.cfg {name}.tla, with SafetyKernel.tla and
+\* formal_specs/FioletSafetyKernel.tla on the library path.
+
+EXTENDS Integers, Reals, Sequences
+
+\* Finite-float abstraction. A finite f32 x is written as its order key:
+\* its bit pattern read as a sign-magnitude integer. For finite x, y:
+\*   x < y <=> Key(x) < Key(y),  x = y <=> Key(x) = Key(y) (-0 = +0),
+\*   Key(-x) = -Key(x).
+\* The config overrides Real with FiniteFloat, so `\notin Real` holds for
+\* exactly NaN and +/-Inf, written as the strings below.
+FiniteFloat == -{max}..{max}
+NaN    == "NaN"
+PosInf == "+Inf"
+NegInf == "-Inf"
+
+Limit == {limit}
+
+\* phase "input": deviation chosen, decision of this evaluation recorded.
+\* phase "decided": after the kernel step.
+Trace == <<
+"#,
+        steps = trace.steps.len(),
+        limit_value = trace.config.deviation_limit,
+        limit_bits = trace.config.deviation_limit.to_bits(),
+        max = MAX_FLOAT_KEY,
+    );
+
+    let mut rows = vec![(
+        false,
+        "0".to_owned(),
+        SafetyDecision::Continue,
+        "decided",
+        None,
+    )];
+    let mut halted = false;
+    for step in &trace.steps {
+        let deviation = value(step.deviation);
+        rows.push((
+            halted,
+            deviation.clone(),
+            step.decision,
+            "input",
+            Some(step.deviation),
+        ));
+        halted |= step.decision == SafetyDecision::AtomicHalt;
+        rows.push((halted, deviation, step.decision, "decided", None));
+    }
+    for (index, (halted, deviation, step_decision, phase, raw)) in rows.iter().enumerate() {
+        let separator = if index + 1 == rows.len() { "" } else { "," };
+        let _ = write!(
+            out,
+            "  [halted |-> {}, deviation |-> {deviation}, decision |-> {}, phase |-> \"{phase}\"]{separator}",
+            if *halted { "TRUE" } else { "FALSE" },
+            decision(*step_decision),
+        );
+        match raw {
+            Some(raw) => {
+                let _ = writeln!(out, " \\* {raw:?} ({:#010x})", raw.to_bits());
+            }
+            None => out.push('\n'),
+        }
+    }
+
+    let _ = write!(
+        out,
+        r#">>
+
+VARIABLES halted, deviation, limit, decision, phase, i
+
+vars == <<halted, deviation, limit, decision, phase>>
+
+SK == INSTANCE SafetyKernel WITH DeviationLimit <- Limit
+FK == INSTANCE FioletSafetyKernel
+        WITH metric <- deviation, baseline <- 0, threshold <- limit
+
+TraceInit ==
+    /\ i = 1
+    /\ halted = Trace[1].halted
+    /\ deviation = Trace[1].deviation
+    /\ limit = Limit
+    /\ decision = Trace[1].decision
+    /\ phase = Trace[1].phase
+
+TraceNext ==
+    /\ i < Len(Trace)
+    /\ i' = i + 1
+    /\ halted' = Trace[i'].halted
+    /\ deviation' = Trace[i'].deviation
+    /\ limit' = limit
+    /\ decision' = Trace[i'].decision
+    /\ phase' = Trace[i'].phase
+
+TraceSpec == TraceInit /\ [][TraceNext]_<<vars, i>>
+
+\* The environment chooses the next deviation; nothing else changes.
+EnvStep ==
+    /\ phase = "decided" /\ phase' = "input"
+    /\ UNCHANGED <<halted, limit>>
+
+\* The kernel evaluates `deviation`; `decision` is its result.
+KernelStep ==
+    /\ phase = "input" /\ phase' = "decided"
+    /\ UNCHANGED decision
+    /\ decision = IF halted' THEN "ATOMIC_HALT" ELSE "CONTINUE"
+
+SafetyKernelInit == SK!Init
+SafetyKernelConformance == [][EnvStep \/ (KernelStep /\ SK!Next)]_vars
+SafetyKernelInvariants == [][SK!I1_MonotonicHalt /\ SK!I3_FailClosed]_vars
+
+FioletKernelInit == FK!Init
+FioletKernelConformance ==
+    [][EnvStep \/ (KernelStep /\ FK!Next /\ UNCHANGED <<deviation, limit>>)]_vars
+
+=============================================================================
+"#
+    );
+    out
+}
+
+fn config(fiolet_kernel: FioletKernelCheck) -> String {
+    let mut out = String::from(
+        r"\* TLC configuration for the exported trace module.
+INIT TraceInit
+NEXT TraceNext
+CHECK_DEADLOCK FALSE
+
+\* Finite-float abstraction (see the module header).
+CONSTANT Real <- FiniteFloat
+
+\* State predicates as PROPERTY are checked on the initial state.
+PROPERTY SafetyKernelInit
+PROPERTY SafetyKernelConformance
+PROPERTY SafetyKernelInvariants
+",
+    );
+    out.push_str(match fiolet_kernel {
+        FioletKernelCheck::Applies => {
+            "PROPERTY FioletKernelInit\nPROPERTY FioletKernelConformance\n"
+        }
+        FioletKernelCheck::NonFinite => {
+            r"\* FioletKernel properties omitted: the trace has non-finite deviations
+\* and FioletSafetyKernel.tla admits only metric \in Real.
+"
+        }
+        FioletKernelCheck::BelowLowerBand => {
+            r"\* FioletKernel properties omitted: the trace has deviations below -limit.
+\* FioletSafetyKernel.tla bounds |metric - baseline|; the kernel bounds
+\* only deviation > limit, so these steps need not halt.
+"
+        }
+    });
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::trace::TraceRecorder;
+
+    #[test]
+    fn float_key_preserves_order_and_negation() {
+        let values = [
+            f32::MIN,
+            -1.0,
+            -f32::MIN_POSITIVE,
+            -f32::from_bits(1),
+            -0.0,
+            0.0,
+            f32::from_bits(1),
+            f32::MIN_POSITIVE,
+            0.5,
+            1.0,
+            f32::MAX,
+        ];
+        for x in values {
+            assert_eq!(float_key(-x), float_key(x).map(|key| -key));
+            for y in values {
+                let (kx, ky) = (float_key(x).unwrap(), float_key(y).unwrap());
+                assert_eq!(x < y, kx < ky, "{x:?} < {y:?}");
+                assert_eq!(x == y, kx == ky, "{x:?} == {y:?}");
+            }
+        }
+        assert_eq!(float_key(f32::MAX), Some(MAX_FLOAT_KEY));
+        assert_eq!(float_key(f32::NAN), None);
+        assert_eq!(float_key(f32::NEG_INFINITY), None);
+    }
+
+    #[test]
+    fn export_writes_boot_input_and_decided_states() {
+        let mut recorder = TraceRecorder::new(SafetyConfig::new(1.0));
+        recorder.evaluate(0.5);
+        recorder.evaluate(f32::NAN);
+        recorder.evaluate(0.0);
+
+        let tla = export(recorder.trace(), "Run_1").unwrap();
+        assert!(tla.module.starts_with("----"));
+        assert!(tla.module.contains(" MODULE Run_1 "));
+        assert!(tla.module.contains("Limit == 1065353216\n"));
+
+        let rows: Vec<&str> = tla
+            .module
+            .lines()
+            .filter(|line| line.starts_with("  [halted"))
+            .collect();
+        assert_eq!(rows.len(), 1 + 2 * 3);
+        assert!(rows[0].contains("halted |-> FALSE, deviation |-> 0,"));
+        assert!(rows[1].contains("deviation |-> 1056964608, decision |-> \"CONTINUE\""));
+        assert!(rows[3].contains("halted |-> FALSE, deviation |-> NaN,"));
+        assert!(rows[3].contains("\"ATOMIC_HALT\""));
+        assert!(rows[4].starts_with("  [halted |-> TRUE, deviation |-> NaN,"));
+        assert!(rows[6].ends_with("phase |-> \"decided\"]"));
+
+        // A non-finite input rules out the FioletSafetyKernel check.
+        assert!(tla.config.contains("PROPERTY SafetyKernelConformance\n"));
+        assert!(!tla.config.contains("PROPERTY FioletKernelConformance"));
+        assert!(tla.config.contains("CONSTANT Real <- FiniteFloat\n"));
+    }
+
+    #[test]
+    fn deviations_below_minus_limit_omit_fiolet_kernel_properties() {
+        let mut recorder = TraceRecorder::new(SafetyConfig::new(1.0));
+        recorder.evaluate(-5.0);
+        recorder.evaluate(0.5);
+        assert!(!recorder.kernel().is_halted());
+
+        let tla = export(recorder.trace(), "Below").unwrap();
+        assert!(tla.config.contains("PROPERTY SafetyKernelConformance\n"));
+        assert!(!tla.config.contains("PROPERTY FioletKernel"));
+        assert!(tla.config.contains("deviations below -limit"));
+
+        // At exactly -limit both specs agree: |deviation| is not > limit.
+        let mut recorder = TraceRecorder::new(SafetyConfig::new(1.0));
+        recorder.evaluate(-1.0);
+        let tla = export(recorder.trace(), "AtLimit").unwrap();
+        assert!(tla.config.contains("PROPERTY FioletKernelConformance\n"));
+    }
+
+    #[test]
+    fn export_rejects_what_tla_cannot_express() {
+        let trace = TraceRecorder::new(SafetyConfig::new(1.0)).finish();
+        assert_eq!(export(&trace, "1st").err(), Some(ExportError::InvalidName));
+        assert_eq!(export(&trace, "a-b").err(), Some(ExportError::InvalidName));
+        assert!(export(&trace, "Finite")
+            .unwrap()
+            .config
+            .contains("PROPERTY FioletKernelConformance"));
+
+        let trace = TraceRecorder::new(SafetyConfig::new(f32::INFINITY)).finish();
+        assert_eq!(export(&trace, "T").err(), Some(ExportError::NonFiniteLimit));
+
+        let trace = TraceRecorder::new(SafetyConfig::relative(5.0, 1.0)).finish();
+        assert_eq!(export(&trace, "T").err(), Some(ExportError::BaselineBand));
+
+        for config in [
+            SafetyConfig::new(1.0).with_window(0.5, 2, 3),
+            SafetyConfig::new(1.0).with_slope_limit(0.5),
+            SafetyConfig::new(-1.0),
+        ] {
+            let trace = TraceRecorder::new(config).finish();
+            assert_eq!(export(&trace, "T").err(), Some(ExportError::UnmodelledRule));
+        }
+    }
+
+    #[test]
+    fn step_budget_halts_are_not_exported() {
+        let mut recorder = TraceRecorder::new(SafetyConfig::new(1.0).with_max_steps(1));
+        assert_eq!(recorder.evaluate(0.5).decision(), SafetyDecision::Continue);
+        assert_eq!(
+            recorder.evaluate(0.5).decision(),
+            SafetyDecision::AtomicHalt
+        );
+
+        assert_eq!(
+            export(recorder.trace(), "Budget").err(),
+            Some(ExportError::UnmodelledRule)
+        );
+    }
+}
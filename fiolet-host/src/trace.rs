@@ -0,0 +1,412 @@
+//! Deterministic decision traces and replay (RFC-0003 §6: reproduction
+//! of HALT cases).
+//!
+//! A `TraceRecorder` wraps a `SafetyKernel` and records the configuration,
+//! every evaluated deviation and every decision. `Trace::encode` turns it
+//! into a compact binary that decodes identically on every platform;
+//! `replay` re-runs it against the current kernel and lists every step
+//! whose decision differs from the recording.
+//!
+//! Format (version 2, all integers little-endian, floats as IEEE-754 bit
+//! patterns so NaN payloads and signed zeros survive):
+//!
+//! ```text
+//! header   40 bytes
+//!   0   magic            b"FTRC"
+//!   4   version          u16 (2)
+//!   6   reserved         u16 (0)
+//!   8   deviation_limit  u32  f32 bits
+//!   12  max_steps        u64
+//!   20  slope_limit      u32  f32 bits
+//!   24  window.soft      u32  f32 bits
+//!   28  window.k         u8
+//!   29  window.n         u8
+//!   30  reserved         u16 (0)
+//!   32  baseline         u32  f32 bits
+//!   36  lower_limit      u32  f32 bits
+//! steps    5 bytes each
+//!   0   deviation        u32  f32 bits
+//!   4   decision         u8   SafetyDecision code
+//! footer   16 bytes
+//!   0   step count       u64
+//!   8   checksum         u64  FNV-1a of everything before it
+//! ```
+//!
+//! Version 1 traces (32-byte header, ending before `baseline`) predate
+//! baseline-relative configurations; they still decode, with the
+//! defaults of `SafetyConfig::new`.
+
+use core::fmt;
+
+use fiolet_core::{SafetyConfig, SafetyDecision, SafetyKernel, Verdict, WindowConfig};
+
+use crate::hash::fnv1a64;
+
+const MAGIC: &[u8; 4] = b"FTRC";
+
+/// Trace format version written by this build.
+pub const TRACE_VERSION: u16 = 2;
+
+const HEADER_LEN: usize = 40;
+const HEADER_LEN_V1: usize = 32;
+const STEP_LEN: usize = 5;
+const FOOTER_LEN: usize = 16;
+
+/// Why a byte string is not a valid trace.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TraceError {
+    /// Shorter than its header and footer, or not a whole number of steps.
+    Truncated,
+    /// Does not start with the trace magic.
+    BadMagic,
+    /// Written by a format version this build does not read.
+    UnsupportedVersion(u16),
+    /// Footer checksum or step count does not match the contents.
+    Checksum,
+    /// Step `index` carries an unknown decision code.
+    BadDecision { index: usize },
+}
+
+impl fmt::Display for TraceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraceError::Truncated => write!(f, "trace is truncated"),
+            TraceError::BadMagic => write!(f, "not a fiolet trace"),
+            TraceError::UnsupportedVersion(version) => {
+                write!(f, "unsupported trace version {version}")
+            }
+            TraceError::Checksum => write!(f, "trace checksum mismatch"),
+            TraceError::BadDecision { index } => write!(f, "step {index}: unknown decision code"),
+        }
+    }
+}
+
+impl std::error::Error for TraceError {}
+
+/// One evaluation: the input and what the kernel decided.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct TraceStep {
+    pub deviation: f32,
+    pub decision: SafetyDecision,
+}
+
+/// Configuration plus every evaluation of one kernel.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Trace {
+    pub config: SafetyConfig,
+    pub steps: Vec<TraceStep>,
+}
+
+impl Trace {
+    /// Binary encoding (see the module docs).
+    pub fn encode(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(HEADER_LEN + self.steps.len() * STEP_LEN + FOOTER_LEN);
+        let config = &self.config;
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&TRACE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&config.deviation_limit.to_bits().to_le_bytes());
+        bytes.extend_from_slice(&config.max_steps.to_le_bytes());
+        bytes.extend_from_slice(&config.slope_limit.to_bits().to_le_bytes());
+        bytes.extend_from_slice(&config.window.soft_limit.to_bits().to_le_bytes());
+        bytes.push(config.window.k);
+        bytes.push(config.window.n);
+        bytes.extend_from_slice(&0u16.to_le_bytes());
+        bytes.extend_from_slice(&config.baseline.to_bits().to_le_bytes());
+        bytes.extend_from_slice(&config.lower_limit.to_bits().to_le_bytes());
+
+        for step in &self.steps {
+            bytes.extend_from_slice(&step.deviation.to_bits().to_le_bytes());
+            bytes.push(step.decision as u8);
+        }
+
+        bytes.extend_from_slice(&(self.steps.len() as u64).to_le_bytes());
+        let checksum = fnv1a64(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+        bytes
+    }
+
+    /// Parse and verify an encoded trace.
+    pub fn decode(bytes: &[u8]) -> Result<Self, TraceError> {
+        if bytes.len() < HEADER_LEN_V1 + FOOTER_LEN {
+            return Err(TraceError::Truncated);
+        }
+        if &bytes[0..4] != MAGIC {
+            return Err(TraceError::BadMagic);
+        }
+        let header_len = match u16::from_le_bytes([bytes[4], bytes[5]]) {
+            1 => HEADER_LEN_V1,
+            TRACE_VERSION => HEADER_LEN,
+            version => return Err(TraceError::UnsupportedVersion(version)),
+        };
+        if bytes.len() < header_len + FOOTER_LEN {
+            return Err(TraceError::Truncated);
+        }
+
+        let body_end = bytes.len() - FOOTER_LEN;
+        // `usize::is_multiple_of` needs Rust 1.87.
+        #[allow(clippy::manual_is_multiple_of)]
+        if (body_end - header_len) % STEP_LEN != 0 {
+            return Err(TraceError::Truncated);
+        }
+        let count = (body_end - header_len) / STEP_LEN;
+        if u64_at(bytes, body_end) != count as u64
+            || u64_at(bytes, body_end + 8) != fnv1a64(&bytes[..body_end + 8])
+            || bytes[6..8] != [0, 0]
+            || bytes[30..32] != [0, 0]
+        {
+            return Err(TraceError::Checksum);
+        }
+
+        let mut config = SafetyConfig {
+            max_steps: u64_at(bytes, 12),
+            slope_limit: f32_at(bytes, 20),
+            window: WindowConfig::new(f32_at(bytes, 24), bytes[28], bytes[29]),
+            ..SafetyConfig::new(f32_at(bytes, 8))
+        };
+        if header_len == HEADER_LEN {
+            config.baseline = f32_at(bytes, 32);
+            config.lower_limit = f32_at(bytes, 36);
+        }
+
+        let steps = bytes[header_len..body_end]
+            .chunks_exact(STEP_LEN)
+            .enumerate()
+            .map(|(index, step)| {
+                let decision = match step[4] {
+                    0 => SafetyDecision::Continue,
+                    1 => SafetyDecision::AtomicHalt,
+                    _ => return Err(TraceError::BadDecision { index }),
+                };
+                Ok(TraceStep {
+                    deviation: f32_at(step, 0),
+                    decision,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { config, steps })
+    }
+}
+
+fn u64_at(bytes: &[u8], at: usize) -> u64 {
+    let mut word = [0u8; 8];
+    word.copy_from_slice(&bytes[at..at + 8]);
+    u64::from_le_bytes(word)
+}
+
+fn f32_at(bytes: &[u8], at: usize) -> f32 {
+    let mut word = [0u8; 4];
+    word.copy_from_slice(&bytes[at..at + 4]);
+    f32::from_bits(u32::from_le_bytes(word))
+}
+
+/// `SafetyKernel` that records every evaluation.
+pub struct TraceRecorder {
+    kernel: SafetyKernel,
+    trace: Trace,
+}
+
+impl TraceRecorder {
+    /// Start a kernel and an empty trace for `config`.
+    pub fn new(config: SafetyConfig) -> Self {
+        Self {
+            kernel: SafetyKernel::new(config),
+            trace: Trace {
+                config,
+                steps: Vec::new(),
+            },
+        }
+    }
+
+    /// Evaluate a deviation and record input and decision.
+    pub fn evaluate(&mut self, deviation: f32) -> Verdict {
+        let verdict = self.kernel.evaluate(deviation);
+        self.trace.steps.push(TraceStep {
+            deviation,
+            decision: verdict.decision(),
+        });
+        verdict
+    }
+
+    /// The wrapped kernel.
+    pub fn kernel(&self) -> &SafetyKernel {
+        &self.kernel
+    }
+
+    /// Trace recorded so far.
+    pub fn trace(&self) -> &Trace {
+        &self.trace
+    }
+
+    /// Stop recording and hand back the trace.
+    pub fn finish(self) -> Trace {
+        self.trace
+    }
+}
+
+/// A step whose replayed decision differs from the recorded one.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Divergence {
+    /// Zero-based step index.
+    pub index: usize,
+    pub deviation: f32,
+    pub recorded: SafetyDecision,
+    pub replayed: SafetyDecision,
+}
+
+/// Re-run `trace` against the current kernel with its recorded config.
+///
+/// Empty result: this build reproduces every decision.
+pub fn replay(trace: &Trace) -> Vec<Divergence> {
+    replay_with(trace, trace.config)
+}
+
+/// Re-run the inputs of `trace` with a different `config` (e.g. a
+/// proposed threshold change) and compare against the recording.
+pub fn replay_with(trace: &Trace, config: SafetyConfig) -> Vec<Divergence> {
+    let mut kernel = SafetyKernel::new(config);
+    trace
+        .steps
+        .iter()
+        .enumerate()
+        .filter_map(|(index, step)| {
+            let replayed = kernel.evaluate(step.deviation).decision();
+            (replayed != step.decision).then_some(Divergence {
+                index,
+                deviation: step.deviation,
+                recorded: step.decision,
+                replayed,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded() -> Trace {
+        let config = SafetyConfig::new(1.0)
+            .with_max_steps(100)
+            .with_window(0.5, 2, 4)
+            .with_slope_limit(0.75);
+        let mut recorder = TraceRecorder::new(config);
+        for deviation in [0.1, -0.0, 0.6, 0.2, 0.7, f32::NAN, 0.0] {
+            recorder.evaluate(deviation);
+        }
+        assert!(recorder.kernel().is_halted());
+        recorder.finish()
+    }
+
+    #[test]
+    fn encoding_round_trips_bit_exactly() {
+        let trace = recorded();
+        let bytes = trace.encode();
+        assert_eq!(bytes.len(), HEADER_LEN + 7 * STEP_LEN + FOOTER_LEN);
+
+        let decoded = Trace::decode(&bytes).unwrap();
+        assert_eq!(decoded.encode(), bytes);
+        assert_eq!(decoded.steps[1].deviation.to_bits(), (-0.0f32).to_bits());
+        assert!(decoded.steps[5].deviation.is_nan());
+        assert_eq!(decoded.config.window, trace.config.window);
+        assert_eq!(decoded.config.max_steps, 100);
+    }
+
+    #[test]
+    fn header_layout_is_pinned() {
+        let bytes = Trace {
+            config: SafetyConfig::new(1.0),
+            steps: vec![TraceStep {
+                deviation: 2.0,
+                decision: SafetyDecision::AtomicHalt,
+            }],
+        }
+        .encode();
+
+        assert_eq!(&bytes[0..8], b"FTRC\x02\x00\x00\x00");
+        assert_eq!(&bytes[8..12], &[0x00, 0x00, 0x80, 0x3f]);
+        assert_eq!(&bytes[20..24], &[0x00, 0x00, 0x80, 0x7f]);
+        assert_eq!(&bytes[32..40], &[0, 0, 0, 0, 0x00, 0x00, 0x80, 0x7f]);
+        assert_eq!(&bytes[40..45], &[0x00, 0x00, 0x00, 0x40, 0x01]);
+        assert_eq!(&bytes[45..53], &1u64.to_le_bytes());
+    }
+
+    #[test]
+    fn version_1_traces_decode_with_default_band() {
+        let mut bytes = b"FTRC\x01\x00\x00\x00".to_vec();
+        bytes.extend_from_slice(&1.0f32.to_bits().to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+        bytes.extend_from_slice(&f32::INFINITY.to_bits().to_le_bytes());
+        bytes.extend_from_slice(&[0; 8]);
+        bytes.extend_from_slice(&(-2.0f32).to_bits().to_le_bytes());
+        bytes.push(0);
+        bytes.extend_from_slice(&1u64.to_le_bytes());
+        let checksum = fnv1a64(&bytes);
+        bytes.extend_from_slice(&checksum.to_le_bytes());
+
+        let trace = Trace::decode(&bytes).unwrap();
+        assert_eq!(trace.config, SafetyConfig::new(1.0));
+        assert_eq!(replay(&trace), []);
+    }
+
+    #[test]
+    fn baseline_band_round_trips() {
+        let mut recorder =
+            TraceRecorder::new(SafetyConfig::relative(50.0, 1.0).with_band(0.5, 2.0));
+        for metric in [51.5, 49.75, 49.25] {
+            recorder.evaluate(metric);
+        }
+        let trace = recorder.finish();
+
+        let decoded = Trace::decode(&trace.encode()).unwrap();
+        assert_eq!(decoded, trace);
+        assert_eq!(decoded.steps[2].decision, SafetyDecision::AtomicHalt);
+        assert_eq!(replay(&decoded), []);
+    }
+
+    #[test]
+    fn damaged_traces_are_rejected() {
+        let bytes = recorded().encode();
+
+        assert_eq!(Trace::decode(&bytes[..20]), Err(TraceError::Truncated));
+        assert_eq!(
+            Trace::decode(&bytes[..bytes.len() - 1]),
+            Err(TraceError::Truncated)
+        );
+
+        let mut bad = bytes.clone();
+        bad[0] = b'X';
+        assert_eq!(Trace::decode(&bad), Err(TraceError::BadMagic));
+
+        let mut bad = bytes.clone();
+        bad[4] = 3;
+        assert_eq!(Trace::decode(&bad), Err(TraceError::UnsupportedVersion(3)));
+
+        for index in 6..bytes.len() {
+            let mut bad = bytes.clone();
+            bad[index] ^= 0x01;
+            assert!(Trace::decode(&bad).is_err(), "byte {index}");
+        }
+    }
+
+    #[test]
+    fn replay_reports_only_differing_decisions() {
+        let trace = recorded();
+        assert_eq!(replay(&trace), []);
+
+        // A tighter window limit halts earlier than recorded.
+        let tighter = trace.config.with_window(0.15, 2, 4);
+        let divergences = replay_with(&trace, tighter);
+        assert_eq!(
+            divergences,
+            [Divergence {
+                index: 3,
+                deviation: 0.2,
+                recorded: SafetyDecision::Continue,
+                replayed: SafetyDecision::AtomicHalt,
+            }]
+        );
+    }
+}
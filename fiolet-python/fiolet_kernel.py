@@ -30,26 +30,75 @@ _lib = ctypes.CDLL(LIB_PATH)
 # C ABI bindings
 # --------------------------------------------------
 
-class SafetyKernel(ctypes.Structure):
+class HaltCause(ctypes.Structure):
+    _fields_ = [
+        ("reason", ctypes.c_int),
+        ("value", ctypes.c_float),
+        ("step", ctypes.c_uint64),
+        ("channel", ctypes.c_uint32),
+    ]
+
+class WindowConfig(ctypes.Structure):
+    _fields_ = [
+        ("soft_limit", ctypes.c_float),
+        ("k", ctypes.c_uint8),
+        ("n", ctypes.c_uint8),
+    ]
+
+class SafetyConfig(ctypes.Structure):
     _fields_ = [
         ("deviation_limit", ctypes.c_float),
-        ("halted", ctypes.c_bool),
+        ("max_steps", ctypes.c_uint64),
+        ("window", WindowConfig),
+        ("slope_limit", ctypes.c_float),
+    ]
+
+class SafetyConfigV2(ctypes.Structure):
+    _fields_ = [
+        ("base", SafetyConfig),
+        ("baseline", ctypes.c_float),
+        ("lower_limit", ctypes.c_float),
+        ("lower_bounded", ctypes.c_uint8),
     ]
 
 _lib.fiolet_kernel_new.argtypes = [ctypes.c_float]
-_lib.fiolet_kernel_new.restype = SafetyKernel
+_lib.fiolet_kernel_new.restype = ctypes.c_void_p
+
+_lib.fiolet_kernel_new_with_config.argtypes = [SafetyConfig]
+_lib.fiolet_kernel_new_with_config.restype = ctypes.c_void_p
+
+_lib.fiolet_kernel_new_with_config_v2.argtypes = [SafetyConfigV2]
+_lib.fiolet_kernel_new_with_config_v2.restype = ctypes.c_void_p
 
 _lib.fiolet_kernel_evaluate.argtypes = [
-    ctypes.POINTER(SafetyKernel),
+    ctypes.c_void_p,
     ctypes.c_float,
 ]
-_lib.fiolet_kernel_evaluate.restype = ctypes.c_uint8
+_lib.fiolet_kernel_evaluate.restype = ctypes.c_int
 
-_lib.fiolet_kernel_is_halted.argtypes = [
-    ctypes.POINTER(SafetyKernel)
+_lib.fiolet_kernel_evaluate_slice.argtypes = [
+    ctypes.c_void_p,
+    ctypes.POINTER(ctypes.c_float),
+    ctypes.c_size_t,
+    ctypes.POINTER(ctypes.c_size_t),
 ]
+_lib.fiolet_kernel_evaluate_slice.restype = ctypes.c_int
+
+_lib.fiolet_kernel_is_halted.argtypes = [ctypes.c_void_p]
 _lib.fiolet_kernel_is_halted.restype = ctypes.c_bool
 
+_lib.fiolet_kernel_steps.argtypes = [ctypes.c_void_p]
+_lib.fiolet_kernel_steps.restype = ctypes.c_uint64
+
+_lib.fiolet_kernel_halt_cause.argtypes = [
+    ctypes.c_void_p,
+    ctypes.POINTER(HaltCause),
+]
+_lib.fiolet_kernel_halt_cause.restype = ctypes.c_bool
+
+_lib.fiolet_kernel_free.argtypes = [ctypes.c_void_p]
+_lib.fiolet_kernel_free.restype = None
+
 # --------------------------------------------------
 # Python API
 # --------------------------------------------------
@@ -58,18 +107,76 @@ class FioletKernel:
     CONTINUE = 0
     ATOMIC_HALT = 1
 
-    def __init__(self, deviation_limit: float):
-        self._kernel = _lib.fiolet_kernel_new(
-            ctypes.c_float(deviation_limit)
+    def __init__(
+        self,
+        deviation_limit: float,
+        max_steps: int = 0,
+        window: tuple = (0.0, 0, 0),
+        slope_limit: float = float("inf"),
+        baseline: float = 0.0,
+        lower_limit: float = None,
+    ):
+        config = SafetyConfig(
+            deviation_limit,
+            max_steps,
+            WindowConfig(*window),
+            slope_limit,
         )
+        if baseline == 0.0 and lower_limit is None:
+            self._kernel = _lib.fiolet_kernel_new_with_config(config)
+        else:
+            self._kernel = _lib.fiolet_kernel_new_with_config_v2(
+                SafetyConfigV2(
+                    config,
+                    baseline,
+                    0.0 if lower_limit is None else lower_limit,
+                    lower_limit is not None,
+                )
+            )
+
+    def __del__(self):
+        kernel, self._kernel = getattr(self, "_kernel", None), None
+        if kernel:
+            _lib.fiolet_kernel_free(kernel)
 
     def evaluate(self, deviation: float) -> int:
         return _lib.fiolet_kernel_evaluate(
-            ctypes.byref(self._kernel),
+            self._kernel,
             ctypes.c_float(deviation),
         )
 
+    def evaluate_many(self, deviations):
+        """Evaluate a sequence in one FFI call.
+
+        Returns (decision, index of first halting element or None).
+        """
+        values = (ctypes.c_float * len(deviations))(*deviations)
+        first_halt = ctypes.c_size_t()
+        decision = _lib.fiolet_kernel_evaluate_slice(
+            self._kernel,
+            values,
+            len(deviations),
+            ctypes.byref(first_halt),
+        )
+        if first_halt.value == ctypes.c_size_t(-1).value:
+            return decision, None
+        return decision, first_halt.value
+
     def is_halted(self) -> bool:
         return _lib.fiolet_kernel_is_halted(
-            ctypes.byref(self._kernel)
+            self._kernel
         )
+
+    def steps(self) -> int:
+        return _lib.fiolet_kernel_steps(
+            self._kernel
+        )
+
+    def halt_cause(self):
+        cause = HaltCause()
+        if not _lib.fiolet_kernel_halt_cause(
+            self._kernel,
+            ctypes.byref(cause),
+        ):
+            return None
+        return cause
@@ -7,6 +7,17 @@ pub enum Decision {
     AtomicHalt,
 }
 
+/// Reason a kernel configuration was rejected
+#[derive(Copy, Clone, Eq, PartialEq, Debug)]
+pub enum ConfigError {
+    /// Baseline is NaN or infinite
+    NonFiniteBaseline,
+    /// Threshold is NaN or infinite
+    NonFiniteThreshold,
+    /// Threshold is negative
+    NegativeThreshold,
+}
+
 /// Configuration of the safety kernel
 #[derive(Copy, Clone)]
 pub struct KernelConfig {
@@ -14,6 +25,30 @@ pub struct KernelConfig {
     pub threshold: f32,
 }
 
+impl KernelConfig {
+    /// Create a configuration, rejecting invalid values
+    pub const fn try_new(baseline: f32, threshold: f32) -> Result<Self, ConfigError> {
+        let config = Self { baseline, threshold };
+        match config.validate() {
+            Ok(()) => Ok(config),
+            Err(error) => Err(error),
+        }
+    }
+
+    /// Baseline must be finite; threshold finite and non-negative
+    pub const fn validate(&self) -> Result<(), ConfigError> {
+        if !self.baseline.is_finite() {
+            Err(ConfigError::NonFiniteBaseline)
+        } else if !self.threshold.is_finite() {
+            Err(ConfigError::NonFiniteThreshold)
+        } else if self.threshold < 0.0 {
+            Err(ConfigError::NegativeThreshold)
+        } else {
+            Ok(())
+        }
+    }
+}
+
 /// Internal state of the kernel
 pub struct SafetyKernel {
     config: KernelConfig,
@@ -22,10 +57,20 @@ pub struct SafetyKernel {
 
 impl SafetyKernel {
     /// Create a new kernel instance
+    ///
+    /// Fail-closed: an invalid configuration yields a halted kernel.
     pub const fn new(config: KernelConfig) -> Self {
         Self {
             config,
-            halted: false,
+            halted: config.validate().is_err(),
+        }
+    }
+
+    /// Create a new kernel instance, rejecting an invalid configuration
+    pub const fn try_new(config: KernelConfig) -> Result<Self, ConfigError> {
+        match config.validate() {
+            Ok(()) => Ok(Self::new(config)),
+            Err(error) => Err(error),
         }
     }
 
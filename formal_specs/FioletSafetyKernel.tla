@@ -16,7 +16,7 @@ Deviation(m) ==
     IF m >= baseline THEN m - baseline ELSE baseline - m
 
 Evaluate ==
-    /\ metric \in Reals
+    /\ metric \in Real
     /\ IF halted = TRUE THEN
            /\ decision = "ATOMIC_HALT"
            /\ halted' = TRUE
@@ -0,0 +1,17 @@
+#!/bin/bash
+# Build the no_std staticlib and run the C ABI conformance test against it.
+set -euo pipefail
+
+ROOT="$(cd "$(dirname "$0")/.." && pwd)"
+TARGET_DIR="${CARGO_TARGET_DIR:-$ROOT/target}"
+CC="${CC:-cc}"
+
+cargo build --release -p fiolet_core --manifest-path "$ROOT/Cargo.toml"
+
+"$CC" -std=c11 -Wall -Wextra -Werror \
+    -I "$ROOT/fiolet-core/include" \
+    "$ROOT/fiolet-core/include/abi_conformance_test.c" \
+    "$TARGET_DIR/release/libfiolet_core.a" \
+    -lm -o "$TARGET_DIR/abi_conformance_test"
+
+"$TARGET_DIR/abi_conformance_test"